@@ -40,9 +40,7 @@
 extern crate std;
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, contracterror, symbol_short,
-    Address, Env, Vec,
-    log,
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, Address, Env, Vec,
 };
 
 /// Schema version stamped onto every emitted event so off-chain indexers can
@@ -189,9 +187,7 @@ impl AccessControlContract {
         // Initialize member list with owner
         let mut members = Vec::new(&env);
         members.push_back(owner.clone());
-        env.storage()
-            .instance()
-            .set(&DataKey::AllMembers, &members);
+        env.storage().instance().set(&DataKey::AllMembers, &members);
 
         // Initialize role counts
         env.storage()
@@ -308,9 +304,7 @@ impl AccessControlContract {
                 .instance()
                 .get(&DataKey::RoleCount(old_role_val))
                 .unwrap_or(1);
-            if old_count > 0 {
-                old_count -= 1;
-            }
+            old_count = old_count.saturating_sub(1);
             env.storage()
                 .instance()
                 .set(&DataKey::RoleCount(old_role_val), &old_count);
@@ -322,9 +316,7 @@ impl AccessControlContract {
                 .get(&DataKey::AllMembers)
                 .unwrap_or(Vec::new(&env));
             members.push_back(target.clone());
-            env.storage()
-                .instance()
-                .set(&DataKey::AllMembers, &members);
+            env.storage().instance().set(&DataKey::AllMembers, &members);
         }
 
         let assignment = RoleAssignment {
@@ -345,7 +337,13 @@ impl AccessControlContract {
 
         env.events().publish(
             (symbol_short!("acl"), symbol_short!("assign")),
-            (assignor, target, old_role_val, role_val, EVENT_SCHEMA_VERSION),
+            (
+                assignor,
+                target,
+                old_role_val,
+                role_val,
+                EVENT_SCHEMA_VERSION,
+            ),
         );
 
         Ok(())
@@ -354,11 +352,7 @@ impl AccessControlContract {
     /// Revoke a role from an address.
     /// Owners cannot be removed. Only owners can revoke admin roles.
     /// An actor cannot revoke its own role.
-    pub fn revoke_role(
-        env: Env,
-        revoker: Address,
-        target: Address,
-    ) -> Result<(), Error> {
+    pub fn revoke_role(env: Env, revoker: Address, target: Address) -> Result<(), Error> {
         Self::require_initialized(&env)?;
 
         revoker.require_auth();
@@ -397,9 +391,7 @@ impl AccessControlContract {
             .instance()
             .get(&DataKey::RoleCount(old_role_val))
             .unwrap_or(1);
-        if count > 0 {
-            count -= 1;
-        }
+        count = count.saturating_sub(1);
         env.storage()
             .instance()
             .set(&DataKey::RoleCount(old_role_val), &count);
@@ -677,9 +669,7 @@ impl AccessControlContract {
             .instance()
             .get(&DataKey::RoleCount(Role::Owner as u32))
             .unwrap_or(1);
-        if owner_count > 0 {
-            owner_count -= 1;
-        }
+        owner_count = owner_count.saturating_sub(1);
         let mut admin_count: u32 = env
             .storage()
             .instance()
@@ -705,18 +695,14 @@ impl AccessControlContract {
         {
             let prev_val = prev.role as u32;
             if prev_val == Role::Admin as u32 {
-                if admin_count > 0 {
-                    admin_count -= 1;
-                }
+                admin_count = admin_count.saturating_sub(1);
             } else {
                 let mut c: u32 = env
                     .storage()
                     .instance()
                     .get(&DataKey::RoleCount(prev_val))
                     .unwrap_or(1);
-                if c > 0 {
-                    c -= 1;
-                }
+                c = c.saturating_sub(1);
                 env.storage()
                     .instance()
                     .set(&DataKey::RoleCount(prev_val), &c);
@@ -728,9 +714,7 @@ impl AccessControlContract {
                 .get(&DataKey::AllMembers)
                 .unwrap_or(Vec::new(&env));
             members.push_back(new_owner.clone());
-            env.storage()
-                .instance()
-                .set(&DataKey::AllMembers, &members);
+            env.storage().instance().set(&DataKey::AllMembers, &members);
         }
 
         owner_count += 1;
@@ -752,9 +736,7 @@ impl AccessControlContract {
         env.storage()
             .instance()
             .set(&DataKey::RoleCount(Role::Admin as u32), &admin_count);
-        env.storage()
-            .instance()
-            .set(&DataKey::Owner, &new_owner);
+        env.storage().instance().set(&DataKey::Owner, &new_owner);
         env.storage().instance().remove(&DataKey::PendingOwner);
 
         env.events().publish(
@@ -766,10 +748,7 @@ impl AccessControlContract {
     }
 
     /// Cancel an in-flight ownership transfer. Only the current owner may cancel.
-    pub fn cancel_ownership_transfer(
-        env: Env,
-        current_owner: Address,
-    ) -> Result<(), Error> {
+    pub fn cancel_ownership_transfer(env: Env, current_owner: Address) -> Result<(), Error> {
         Self::require_initialized(&env)?;
 
         current_owner.require_auth();
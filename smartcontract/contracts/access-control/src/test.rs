@@ -24,13 +24,16 @@ fn test_initialize() {
     assert_eq!(summary.total_members, 1);
     assert_eq!(summary.owner_count, 1);
     assert_eq!(summary.admin_count, 0);
-    assert_eq!(client.is_owner(&owner), true);
+    assert!(client.is_owner(&owner));
 }
 
 #[test]
 fn test_initialize_twice_fails() {
     let (_, owner, client) = setup();
-    assert_eq!(client.try_initialize(&owner), Err(Ok(Error::AlreadyInitialized)));
+    assert_eq!(
+        client.try_initialize(&owner),
+        Err(Ok(Error::AlreadyInitialized))
+    );
 }
 
 // ---------------------------------------------------------------------------
@@ -167,11 +170,11 @@ fn test_revoke_member_ok() {
     let (env, owner, client) = setup();
     let member = Address::generate(&env);
     client.assign_role(&owner, &member, &Role::Member);
-    assert_eq!(client.is_member_or_above(&member), true);
+    assert!(client.is_member_or_above(&member));
 
     client.revoke_role(&owner, &member);
-    assert_eq!(client.is_member_or_above(&member), false);
-    assert_eq!(client.has_role(&member), false);
+    assert!(!client.is_member_or_above(&member));
+    assert!(!client.has_role(&member));
     assert_eq!(client.get_summary().member_count, 0);
 }
 
@@ -225,11 +228,11 @@ fn test_stale_role_after_revoke() {
     let (env, owner, client) = setup();
     let admin = Address::generate(&env);
     client.assign_role(&owner, &admin, &Role::Admin);
-    assert_eq!(client.has_permission(&admin, &Role::Admin), true);
+    assert!(client.has_permission(&admin, &Role::Admin));
     client.revoke_role(&owner, &admin);
     // A revoked role grants no permission afterward.
-    assert_eq!(client.has_permission(&admin, &Role::Viewer), false);
-    assert_eq!(client.is_admin_or_above(&admin), false);
+    assert!(!client.has_permission(&admin, &Role::Viewer));
+    assert!(!client.is_admin_or_above(&admin));
 }
 
 // ---------------------------------------------------------------------------
@@ -247,26 +250,26 @@ fn test_permission_matrix() {
     client.assign_role(&owner, &viewer, &Role::Viewer);
 
     // is_owner
-    assert_eq!(client.is_owner(&owner), true);
-    assert_eq!(client.is_owner(&admin), false);
+    assert!(client.is_owner(&owner));
+    assert!(!client.is_owner(&admin));
 
     // is_admin_or_above
-    assert_eq!(client.is_admin_or_above(&owner), true);
-    assert_eq!(client.is_admin_or_above(&admin), true);
-    assert_eq!(client.is_admin_or_above(&member), false);
+    assert!(client.is_admin_or_above(&owner));
+    assert!(client.is_admin_or_above(&admin));
+    assert!(!client.is_admin_or_above(&member));
 
     // is_member_or_above
-    assert_eq!(client.is_member_or_above(&member), true);
-    assert_eq!(client.is_member_or_above(&viewer), false);
+    assert!(client.is_member_or_above(&member));
+    assert!(!client.is_member_or_above(&viewer));
 
     // has_permission thresholds
-    assert_eq!(client.has_permission(&viewer, &Role::Viewer), true);
-    assert_eq!(client.has_permission(&viewer, &Role::Member), false);
-    assert_eq!(client.has_permission(&admin, &Role::Member), true);
+    assert!(client.has_permission(&viewer, &Role::Viewer));
+    assert!(!client.has_permission(&viewer, &Role::Member));
+    assert!(client.has_permission(&admin, &Role::Member));
 
     // Unknown address has no permission.
     let stranger = Address::generate(&env);
-    assert_eq!(client.has_permission(&stranger, &Role::Viewer), false);
+    assert!(!client.has_permission(&stranger, &Role::Viewer));
 }
 
 #[test]
@@ -278,14 +281,14 @@ fn test_can_assign_matrix() {
     client.assign_role(&owner, &member, &Role::Member);
 
     // Owner can assign Admin/Member/Viewer but never Owner.
-    assert_eq!(client.can_assign(&owner, &Role::Admin), true);
-    assert_eq!(client.can_assign(&owner, &Role::Member), true);
-    assert_eq!(client.can_assign(&owner, &Role::Owner), false);
+    assert!(client.can_assign(&owner, &Role::Admin));
+    assert!(client.can_assign(&owner, &Role::Member));
+    assert!(!client.can_assign(&owner, &Role::Owner));
     // Admin can assign Member/Viewer, not Admin.
-    assert_eq!(client.can_assign(&admin, &Role::Member), true);
-    assert_eq!(client.can_assign(&admin, &Role::Admin), false);
+    assert!(client.can_assign(&admin, &Role::Member));
+    assert!(!client.can_assign(&admin, &Role::Admin));
     // Member can assign nothing.
-    assert_eq!(client.can_assign(&member, &Role::Viewer), false);
+    assert!(!client.can_assign(&member, &Role::Viewer));
 }
 
 // ---------------------------------------------------------------------------
@@ -300,14 +303,14 @@ fn test_two_step_ownership_full_flow() {
     client.propose_ownership(&owner, &new_owner);
     assert_eq!(client.get_pending_owner(), Some(new_owner.clone()));
     // Ownership has not moved yet.
-    assert_eq!(client.is_owner(&owner), true);
-    assert_eq!(client.is_owner(&new_owner), false);
+    assert!(client.is_owner(&owner));
+    assert!(!client.is_owner(&new_owner));
 
     client.accept_ownership(&new_owner);
 
     // New owner is the sole Owner; old owner demoted to Admin.
-    assert_eq!(client.is_owner(&new_owner), true);
-    assert_eq!(client.is_owner(&owner), false);
+    assert!(client.is_owner(&new_owner));
+    assert!(!client.is_owner(&owner));
     assert_eq!(client.get_role(&owner).role, Role::Admin);
     assert_eq!(client.get_pending_owner(), None);
 
@@ -371,7 +374,7 @@ fn test_cancel_ownership_transfer() {
         client.try_accept_ownership(&new_owner),
         Err(Ok(Error::NoPendingOwner))
     );
-    assert_eq!(client.is_owner(&owner), true);
+    assert!(client.is_owner(&owner));
 }
 
 #[test]
@@ -407,8 +410,8 @@ fn test_query_functions() {
     client.assign_role(&owner, &admin, &Role::Admin);
     client.assign_role(&owner, &member, &Role::Member);
 
-    assert_eq!(client.has_role(&owner), true);
-    assert_eq!(client.has_role(&Address::generate(&env)), false);
+    assert!(client.has_role(&owner));
+    assert!(!client.has_role(&Address::generate(&env)));
     assert_eq!(client.get_role_count(&Role::Owner), 1);
     assert_eq!(client.get_role_count(&Role::Admin), 1);
     assert_eq!(client.get_role_count(&Role::Member), 1);
@@ -464,7 +467,12 @@ fn test_ownership_event_payload() {
 // Authorization evidence (selective, non-blanket auth)
 // ---------------------------------------------------------------------------
 
-fn fresh() -> (Env, Address, soroban_sdk::Address, AccessControlContractClient<'static>) {
+fn fresh() -> (
+    Env,
+    Address,
+    soroban_sdk::Address,
+    AccessControlContractClient<'static>,
+) {
     let env = Env::default();
     let contract_id = env.register_contract(None, AccessControlContract);
     let client = AccessControlContractClient::new(&env, &contract_id);
@@ -4,13 +4,29 @@
 extern crate std;
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, contracterror, symbol_short,
-    Address, Env, Symbol, Vec,
-    log,
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
 use stellar_sentinel_access_control::AccessControlContractClient;
-use stellar_sentinel_treasury::TreasuryContractClient;
+use stellar_sentinel_treasury::{GrantTerms, TransactionStatus, TreasuryContractClient};
+
+/// Maximum number of entries returned by a single list page.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Maximum number of options on a multi-choice proposal.
+pub const MAX_CHOICE_OPTIONS: u32 = 10;
+
+/// Support points each member can spread across open conviction requests.
+pub const SUPPORT_POINTS: u32 = 100;
+
+/// Maximum length in bytes of a vote's text reason.
+pub const MAX_REASON_LEN: u32 = 280;
+
+/// Fixed-point scale of conviction parameters (parts per million).
+const PPM: u128 = 1_000_000;
 
 // ============================================================================
 // Error Codes
@@ -45,6 +61,53 @@ pub enum Error {
     NotAMember = 11,
     /// Voting period is still active.
     VotingStillActive = 12,
+    /// Passed proposal is still inside its execution timelock.
+    TimelockActive = 13,
+    /// Proposal is not a passed Funding proposal inside its timelock.
+    RagequitWindowClosed = 14,
+    /// Member voted For a proposal that is still pending.
+    VotedForPendingProposal = 15,
+    /// The treasury rejected the ragequit.
+    RagequitFailed = 16,
+    /// The treasury rejected the grant clawback.
+    ClawbackFailed = 17,
+    /// Funding request names an asset the treasury does not hold.
+    AssetMismatch = 18,
+    /// Proposal has no treasury transaction awaiting an outcome.
+    FundingNotPending = 19,
+    /// The treasury could not be read or rejected the slashed bond.
+    BondFailed = 20,
+    /// Proposal has reached quorum and can no longer be withdrawn by its proposer.
+    CancelNotAllowed = 21,
+    /// Action rules need percentages of at most 100 and a non-zero voting period.
+    InvalidRules = 22,
+    /// Proposal is still in its review period.
+    VotingNotStarted = 23,
+    /// Ballot does not fit the proposal's voting method.
+    InvalidBallot = 24,
+    /// The treasury rejected the elected signer.
+    ElectionFailed = 25,
+    /// Voter has too few voice credits left this period.
+    InsufficientCredits = 26,
+    /// Credit policies need a non-zero period; quadratic votes spend at least one credit.
+    InvalidCredits = 27,
+    /// Conviction parameters need a decay below 1, a max ratio in (0, 1], a
+    /// non-zero weight and a non-zero withdrawal expiry.
+    InvalidConviction = 28,
+    /// Conviction request was not found.
+    ConvictionNotFound = 29,
+    /// Conviction request is no longer open.
+    ConvictionClosed = 30,
+    /// Member's stakes would exceed `SUPPORT_POINTS`.
+    SupportExceeded = 31,
+    /// Conviction has not reached the request's threshold.
+    ConvictionBelowThreshold = 32,
+    /// Voter has not registered a vote key for signed votes.
+    VoteKeyNotSet = 33,
+    /// Signed vote's nonce is not the voter's next nonce.
+    InvalidNonce = 34,
+    /// Vote reason text is longer than `MAX_REASON_LEN` bytes.
+    ReasonTooLong = 35,
 }
 
 // ============================================================================
@@ -63,18 +126,97 @@ pub enum DataKey {
     Members,
     /// Minimum percentage of votes required for quorum (0-100).
     QuorumPercent,
-    /// Duration of voting period in ledger sequence numbers.
+    /// Default duration of voting, in units of the voting clock.
     VotingPeriod,
     /// Counter for proposal IDs.
     ProposalCounter,
-    /// A proposal by its ID.
+    /// A proposal by its ID, in the shape proposals were first stored in.
     Proposal(u64),
-    /// Record of a vote: (proposal_id, voter_address).
+    /// `VoteRecord` of a vote: (proposal_id, voter_address). Votes cast
+    /// before vote records hold a bare `bool`.
     Vote(u64, Address),
     /// The access-control contract address for RBAC enforcement.
     AclAddress,
     /// The treasury contract address for cross-contract fund execution.
     TreasuryAddress,
+    /// Ledgers a passed proposal waits before it can be executed.
+    ExecutionDelay,
+    /// Proposal IDs a member voted For before open For votes were counted.
+    /// No longer written; `ragequit` drops it once they have all settled.
+    YesVotes(Address),
+    /// Milestone terms of a Funding proposal that creates a treasury grant.
+    GrantTerms(u64),
+    /// Explicit treasury parameters of a Funding proposal.
+    FundingRequest(u64),
+    /// Bond escrowed by each new proposal, in the treasury's asset.
+    ProposalBond,
+    /// Parameter a PolicyChange proposal sets when executed.
+    PolicyParam(u64),
+    /// Participation totals over finalized proposals.
+    QuorumStats,
+    /// Voting rules for one proposal action, overriding the global defaults.
+    ActionRules(ProposalAction),
+    /// Whether new proposals' voting windows count ledgers or seconds.
+    VotingClock,
+    /// Review period before voting opens, in units of the voting clock.
+    ReviewDelay,
+    /// Treasury grant ID targeted by a Clawback proposal.
+    ClawbackGrant(u64),
+    /// Options, method and tallies of a multi-choice proposal.
+    Choice(u64),
+    /// Ranked ballot at a position of an instant-runoff proposal's votes.
+    BallotAt(u64, u32),
+    /// How new proposals' votes are weighted.
+    VotingMode,
+    /// Default voice credit allotment and its period.
+    CreditPolicy,
+    /// A member's voice credit allotment, overriding the policy's.
+    MemberCredits(Address),
+    /// Voice credits a member has spent in their latest period.
+    CreditUsage(Address),
+    /// Decay, max ratio and weight of conviction voting.
+    ConvictionParams,
+    /// Conviction request counter.
+    ConvictionCounter,
+    /// Conviction request by ID.
+    ConvictionRequest(u64),
+    /// Support points a member stakes on a conviction request.
+    ConvictionStake(u64, Address),
+    /// Support points a member has staked across all conviction requests.
+    StakedSupport(Address),
+    /// Conviction requests a member has a non-zero stake on.
+    StakedRequests(Address),
+    /// Veto percentage new optimistic proposals are created with.
+    VetoPercent,
+    /// Veto percentage of an optimistic proposal, fixed at creation.
+    Optimistic(u64),
+    /// ed25519 public key a member signs relayed votes with.
+    VoteKey(Address),
+    /// Next nonce a member's signed vote must carry.
+    VoteNonce(Address),
+    /// Number of proposals in a status index. Pending and Active proposals
+    /// share the `Active` index.
+    StatusCount(ProposalStatus),
+    /// Proposal ID at a position of a status index.
+    StatusEntry(ProposalStatus, u32),
+    /// Position of a proposal in its status index.
+    StatusSlot(u64),
+    /// Voter at a position of a proposal's votes, in voting order.
+    VoteAt(u64, u32),
+    /// Number of votes a member has cast.
+    VoterVoteCount(Address),
+    /// Proposal ID at a position of a member's votes, in voting order.
+    VoterVoteAt(Address, u32),
+    /// Number of For votes a member has on proposals that still block
+    /// their ragequit.
+    OpenYesVotes(Address),
+    /// Number of For votes on a proposal.
+    YesVoterCount(u64),
+    /// Voter at a position of a proposal's For votes.
+    YesVoterAt(u64, u32),
+    /// Fields added to a proposal after `Proposal` entries were first
+    /// stored. Missing for proposals created before them.
+    ProposalExt(u64),
 }
 
 /// The type of action a proposal requests.
@@ -91,12 +233,18 @@ pub enum ProposalAction {
     RemoveMember,
     /// A general-purpose proposal.
     General,
+    /// Return a treasury grant's unreleased milestones to the treasury.
+    Clawback,
+    /// Add the winning candidate of a multi-choice election as a treasury signer.
+    ElectSigner,
 }
 
 /// The current status of a proposal.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProposalStatus {
+    /// Proposal is in its review period; voting has not opened yet.
+    Pending,
     /// Proposal is active and accepting votes.
     Active,
     /// Proposal passed and is awaiting execution.
@@ -107,6 +255,15 @@ pub enum ProposalStatus {
     Executed,
     /// Proposal expired without reaching quorum.
     Expired,
+    /// The Funding proposal's treasury transaction was executed.
+    Funded,
+    /// The Funding proposal's treasury transaction was canceled or expired;
+    /// the proposal can be executed again.
+    FundingFailed,
+    /// Withdrawn by the proposer or canceled by the admin before finalization.
+    Canceled,
+    /// Optimistic proposal blocked by vetoes above its veto percentage.
+    Vetoed,
 }
 
 /// A governance proposal.
@@ -131,14 +288,318 @@ pub struct Proposal {
     pub total_votes: u32,
     /// Current status.
     pub status: ProposalStatus,
-    /// Ledger sequence when voting opened.
+    /// Ledger sequence when the proposal was created.
     pub created_at: u32,
-    /// Ledger sequence when voting closes.
+    /// Ledger sequence when voting closes (0 under the Timestamp clock).
     pub ends_at: u32,
     /// Optional: amount requested (for funding proposals).
     pub amount: i128,
     /// Optional: target address (for member add/remove).
     pub target: Address,
+    /// Ledger sequence from which a passed proposal can be executed (0 under
+    /// the Timestamp clock).
+    pub executable_at: u32,
+    /// Treasury transaction created when a Funding proposal executed.
+    pub treasury_tx_id: Option<u64>,
+    /// Bond escrowed from the proposer, in the treasury's asset.
+    pub bond: i128,
+    /// Voting rules for the proposal's action, fixed at creation.
+    pub rules: ActionRules,
+    /// Clock the voting window is measured against, fixed at creation.
+    pub clock: VotingClock,
+    /// Ledger timestamp when the proposal was created.
+    pub created_time: u64,
+    /// Ledger timestamp when voting closes (0 under the Ledger clock).
+    pub ends_time: u64,
+    /// When voting opens after the review period, on the proposal's clock
+    /// (ledger sequence or timestamp).
+    pub voting_starts_at: u64,
+    /// How votes are weighted, fixed at creation.
+    pub mode: VotingMode,
+    /// Ledger timestamp from which a passed proposal can be executed (0 under
+    /// the Ledger clock).
+    pub executable_time: u64,
+}
+
+/// The stored part of a `Proposal` that proposals have always had. Its
+/// fields must not change, or proposals stored by earlier versions of the
+/// contract stop decoding.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct StoredProposal {
+    id: u64,
+    title: Symbol,
+    description: Symbol,
+    action: ProposalAction,
+    proposer: Address,
+    votes_for: u32,
+    votes_against: u32,
+    total_votes: u32,
+    status: ProposalStatus,
+    created_at: u32,
+    ends_at: u32,
+    amount: i128,
+    target: Address,
+}
+
+/// The stored part of a `Proposal` added since proposals were first stored.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ProposalExt {
+    executable_at: u32,
+    treasury_tx_id: Option<u64>,
+    bond: i128,
+    rules: ActionRules,
+    clock: VotingClock,
+    created_time: u64,
+    ends_time: u64,
+    voting_starts_at: u64,
+    mode: VotingMode,
+    executable_time: u64,
+}
+
+/// What a proposal's voting window is measured in.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VotingClock {
+    /// Ledger sequence numbers (`created_at` / `ends_at`).
+    Ledger,
+    /// Seconds of ledger time (`created_time` / `ends_time`).
+    Timestamp,
+}
+
+/// How votes on a yes/no proposal are weighted.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VotingMode {
+    /// Each member's vote counts once (`vote`).
+    OneMemberOneVote,
+    /// Members spend voice credits; a vote weighs the integer square root of
+    /// the credits spent (`vote_quadratic`).
+    Quadratic,
+}
+
+/// Voice credits each member may spend on quadratic votes per period.
+///
+/// Periods are always counted in ledgers from the current ledger sequence,
+/// including for proposals that run on `VotingClock::Timestamp`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditPolicy {
+    /// Credits per member per period, unless overridden with `set_member_credits`.
+    pub allotment: u64,
+    /// Period length in ledgers; unspent credits do not carry over.
+    pub period: u32,
+}
+
+/// Voice credits a member spent in one period.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditUsage {
+    /// Period index (ledger sequence / policy period).
+    pub period: u64,
+    /// Credits spent so far in `period`.
+    pub spent: u64,
+}
+
+/// Voting rules for one kind of proposal action.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActionRules {
+    /// Minimum percentage of members that must vote (0-100).
+    pub quorum_percent: u32,
+    /// Percentage of For/Against votes that must be For (0-100); a proposal
+    /// also needs more For than Against votes.
+    pub approval_percent: u32,
+    /// Duration of voting, in units of the voting clock.
+    pub voting_period: u32,
+}
+
+/// A governance parameter set by an executed `PolicyChange` proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolicyParam {
+    /// Bond escrowed by each new proposal, in the treasury's asset (0 = none).
+    ProposalBond(i128),
+    /// Voting rules for new proposals of an action.
+    ActionRules(ProposalAction, ActionRules),
+}
+
+/// Treasury transaction parameters carried by a Funding proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundingRequest {
+    /// Address receiving the funds.
+    pub recipient: Address,
+    /// Asset to pay in; must be the treasury's asset.
+    pub asset: Address,
+    /// Seconds the treasury transaction stays open after the proposal executes.
+    pub expires_after: u64,
+    /// Memo for the treasury transaction.
+    pub memo: Symbol,
+    /// Spending category, for reporting.
+    pub category: Symbol,
+}
+
+/// Participation across finalized proposals. Canceled proposals are never
+/// finalized, so they do not count toward these totals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuorumStats {
+    /// Proposals finalized.
+    pub finalized: u32,
+    /// Finalized proposals that reached quorum.
+    pub quorum_reached: u32,
+    /// Votes cast on finalized proposals.
+    pub votes_cast: u64,
+    /// Member count summed over finalized proposals at finalization.
+    pub eligible_votes: u64,
+    /// Proposals canceled, kept out of the totals above.
+    pub canceled: u32,
+}
+
+/// How the ballots of a multi-choice proposal are counted.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TallyMethod {
+    /// Each ballot names one option; the most votes wins.
+    Plurality,
+    /// Each ballot approves any number of options; the most approvals wins.
+    Approval,
+    /// Each ballot ranks options; the last-placed option is eliminated until
+    /// one holds a majority of the ballots still counting.
+    InstantRunoff,
+}
+
+/// The options of a multi-choice proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChoiceOptions {
+    /// Named options, e.g. competing vendors.
+    Labels(Vec<Symbol>),
+    /// Signer candidates; the winner is added as a treasury signer.
+    Candidates(Vec<Address>),
+}
+
+/// A multi-choice proposal's options and count. Ballots name options by index.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Choice {
+    pub method: TallyMethod,
+    pub options: ChoiceOptions,
+    /// Votes per option: first preferences under `InstantRunoff`, approvals
+    /// under `Approval`.
+    pub tallies: Vec<u32>,
+    /// Index of the winning option, recorded on finalize.
+    pub winner: Option<u32>,
+}
+
+/// Conviction voting parameters, in parts per million.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConvictionParams {
+    /// Share of conviction retained each ledger (below 1_000_000).
+    pub decay: u32,
+    /// Largest share of the treasury balance a request can ask for.
+    pub max_ratio: u32,
+    /// Scales every threshold; higher needs more support for longer.
+    pub weight: u32,
+    /// Seconds a passed request's treasury withdrawal stays executable.
+    pub expires_after: u64,
+}
+
+/// The state of a conviction request.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConvictionStatus {
+    /// Accumulating conviction.
+    Open,
+    /// Reached its threshold and queued a treasury withdrawal.
+    Passed,
+    /// Withdrawn by its proposer.
+    Withdrawn,
+}
+
+/// A funding request decided by conviction voting rather than a vote.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConvictionRequest {
+    /// Unique request identifier.
+    pub id: u64,
+    /// Member who opened the request.
+    pub proposer: Address,
+    /// Address the treasury pays when the request passes.
+    pub recipient: Address,
+    /// Amount requested, in the treasury's asset.
+    pub amount: i128,
+    /// Memo of the queued treasury withdrawal.
+    pub memo: Symbol,
+    /// Support points currently staked.
+    pub support: u32,
+    /// Conviction as of `updated_at`.
+    pub conviction: u128,
+    /// Ledger sequence conviction was last brought up to date.
+    pub updated_at: u32,
+    pub status: ConvictionStatus,
+    /// Treasury withdrawal queued when the request passed.
+    pub treasury_tx_id: Option<u64>,
+}
+
+/// Why a member voted the way they did.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VoteReason {
+    /// No reason given.
+    None,
+    /// Short rationale, at most `MAX_REASON_LEN` bytes.
+    Text(String),
+    /// Hash of a rationale published elsewhere.
+    Hash(BytesN<32>),
+}
+
+/// A member's vote on a proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteRecord {
+    /// For or against; a veto is against and a multi-choice ballot is for.
+    pub vote_for: bool,
+    /// Options chosen on a multi-choice proposal, otherwise empty.
+    pub choices: Vec<u32>,
+    /// Votes counted: 1, or the quadratic weight.
+    pub weight: u32,
+    /// Reason given with `vote_with_reason`. Quadratic votes, vetoes,
+    /// multi-choice ballots and relayed votes carry none.
+    pub reason: VoteReason,
+    /// Ledger timestamp of the vote (0 for votes cast before vote records).
+    pub timestamp: u64,
+}
+
+/// A vote as listed by `list_votes` and `get_votes_by_voter`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteEntry {
+    /// Proposal voted on.
+    pub proposal_id: u64,
+    /// Member who voted.
+    pub voter: Address,
+    /// The vote as recorded.
+    pub record: VoteRecord,
+}
+
+/// A vote signed off-chain with the voter's vote key, submitted by a relayer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedVote {
+    /// Member whose vote key signed the vote.
+    pub voter: Address,
+    /// Proposal voted on.
+    pub proposal_id: u64,
+    /// `true` to vote in favor, `false` to vote against.
+    pub vote_for: bool,
+    /// The voter's next nonce (see `get_vote_nonce`).
+    pub nonce: u64,
+    /// ed25519 signature over `vote_payload(voter, proposal_id, vote_for, nonce)`.
+    pub signature: BytesN<64>,
 }
 
 /// Governance configuration.
@@ -150,6 +611,12 @@ pub struct GovConfig {
     pub quorum_percent: u32,
     pub voting_period: u32,
     pub proposal_count: u64,
+    pub execution_delay: u32,
+    pub proposal_bond: i128,
+    pub voting_clock: VotingClock,
+    pub review_delay: u32,
+    pub voting_mode: VotingMode,
+    pub veto_percent: u32,
 }
 
 // ============================================================================
@@ -171,7 +638,7 @@ impl GovernanceContract {
     /// * `admin` - The admin address.
     /// * `members` - Initial list of DAO members.
     /// * `quorum_percent` - Minimum vote percentage for quorum (1-100).
-    /// * `voting_period` - Duration of voting in ledger sequences.
+    /// * `voting_period` - Duration of voting in ledger sequences (see `set_voting_clock`).
     /// * `acl_address` - The access-control contract address for RBAC enforcement.
     /// * `treasury_address` - The treasury contract address for cross-contract fund execution.
     pub fn initialize(
@@ -206,15 +673,24 @@ impl GovernanceContract {
         env.storage()
             .instance()
             .set(&DataKey::ProposalCounter, &0_u64);
-        env.storage().instance().set(&DataKey::AclAddress, &acl_address);
-        env.storage().instance().set(&DataKey::TreasuryAddress, &treasury_address);
+        env.storage()
+            .instance()
+            .set(&DataKey::AclAddress, &acl_address);
+        env.storage()
+            .instance()
+            .set(&DataKey::TreasuryAddress, &treasury_address);
 
         env.events().publish(
             (symbol_short!("gov"), symbol_short!("init")),
             (admin.clone(), members.len(), quorum_percent),
         );
 
-        log!(&env, "Governance initialized: {} members, {}% quorum", members.len(), quorum_percent);
+        log!(
+            &env,
+            "Governance initialized: {} members, {}% quorum",
+            members.len(),
+            quorum_percent
+        );
         Ok(())
     }
 
@@ -245,663 +721,4778 @@ impl GovernanceContract {
 
         proposer.require_auth();
 
-        // Get and increment counter
-        let proposal_id: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ProposalCounter)
-            .unwrap_or(0)
-            + 1;
-        env.storage()
-            .instance()
-            .set(&DataKey::ProposalCounter, &proposal_id);
+        Self::store_proposal(&env, &proposer, title, description, action, amount, &target)
+    }
 
-        // Calculate voting end
-        let voting_period: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::VotingPeriod)
-            .unwrap_or(1000);
-        let current_ledger = env.ledger().sequence();
-        let ends_at = current_ledger + voting_period;
+    /// Create a Funding proposal with explicit treasury parameters.
+    ///
+    /// Unlike a Funding proposal from `create_proposal`, which pays `target`
+    /// with a 7-day expiry and memo `gov_fund`, the recipient, asset, expiry,
+    /// memo and category are taken from `request`. The proposal's `target` is
+    /// the proposer.
+    ///
+    /// # Arguments
+    /// * `proposer` - Must be a DAO member.
+    /// * `title` - Short title for the proposal.
+    /// * `description` - Description of what the proposal does.
+    /// * `amount` - Amount requested.
+    /// * `request` - Recipient, asset, expiry, memo and category.
+    pub fn create_funding_proposal(
+        env: Env,
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        amount: i128,
+        request: FundingRequest,
+    ) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &proposer)?;
 
-        let proposal = Proposal {
-            id: proposal_id,
-            title: title.clone(),
+        proposer.require_auth();
+
+        if amount <= 0 || request.expires_after == 0 {
+            return Err(Error::InvalidProposal);
+        }
+
+        let proposal_id = Self::store_proposal(
+            &env,
+            &proposer,
+            title,
             description,
-            action: action.clone(),
-            proposer: proposer.clone(),
-            votes_for: 0,
-            votes_against: 0,
-            total_votes: 0,
-            status: ProposalStatus::Active,
-            created_at: current_ledger,
-            ends_at,
+            ProposalAction::Funding,
             amount,
-            target: target.clone(),
-        };
-
+            &proposer,
+        )?;
         env.storage()
             .persistent()
-            .set(&DataKey::Proposal(proposal_id), &proposal);
-
-        env.events().publish(
-            (symbol_short!("gov"), symbol_short!("propose")),
-            (proposal_id, proposer.clone(), title, action),
-        );
-
-        log!(&env, "Proposal #{} created by {:?}", proposal_id, proposer);
+            .set(&DataKey::FundingRequest(proposal_id), &request);
         Ok(proposal_id)
     }
 
-    // ========================================================================
-    // Voting
-    // ========================================================================
-
-    /// Cast a vote on an active proposal.
+    /// Create a Funding proposal that, once executed, proposes a milestone
+    /// grant in the treasury instead of a lump withdrawal.
     ///
     /// # Arguments
-    /// * `voter` - Must be a DAO member.
-    /// * `proposal_id` - The ID of the proposal to vote on.
-    /// * `vote_for` - `true` to vote in favor, `false` to vote against.
-    pub fn vote(
+    /// * `proposer` - Must be a DAO member.
+    /// * `title` - Short title for the proposal.
+    /// * `description` - Description of what the proposal does.
+    /// * `recipient` - Address paid as milestones are released.
+    /// * `terms` - Reviewers, reviewer threshold and milestones; the amount
+    ///   requested is the sum of the milestones.
+    pub fn create_grant_proposal(
         env: Env,
-        voter: Address,
-        proposal_id: u64,
-        vote_for: bool,
-    ) -> Result<(), Error> {
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        recipient: Address,
+        terms: GrantTerms,
+    ) -> Result<u64, Error> {
         Self::require_initialized(&env)?;
-        Self::require_member(&env, &voter)?;
+        Self::require_member(&env, &proposer)?;
 
-        voter.require_auth();
+        proposer.require_auth();
 
-        // Check if already voted
-        let vote_key = DataKey::Vote(proposal_id, voter.clone());
-        if env.storage().persistent().has(&vote_key) {
-            return Err(Error::AlreadyVoted);
+        if terms.milestones.is_empty() {
+            return Err(Error::InvalidProposal);
+        }
+        let mut amount: i128 = 0;
+        for milestone in terms.milestones.iter() {
+            if milestone.amount <= 0 {
+                return Err(Error::InvalidProposal);
+            }
+            amount = amount
+                .checked_add(milestone.amount)
+                .ok_or(Error::InvalidProposal)?;
         }
 
-        // Load proposal
-        let mut proposal: Proposal = env
-            .storage()
+        let proposal_id = Self::store_proposal(
+            &env,
+            &proposer,
+            title,
+            description,
+            ProposalAction::Funding,
+            amount,
+            &recipient,
+        )?;
+        env.storage()
             .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .ok_or(Error::ProposalNotFound)?;
+            .set(&DataKey::GrantTerms(proposal_id), &terms);
+        Ok(proposal_id)
+    }
 
-        // Check proposal is still active
-        if proposal.status != ProposalStatus::Active {
-            return Err(Error::VotingClosed);
-        }
+    /// Create a proposal to claw back a treasury grant's unreleased
+    /// milestones. The treasury must have this contract registered via
+    /// `propose_governance`.
+    pub fn create_clawback_proposal(
+        env: Env,
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        grant_id: u64,
+    ) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &proposer)?;
 
-        // Check voting period hasn't ended
-        let current_ledger = env.ledger().sequence();
-        if current_ledger > proposal.ends_at {
-            return Err(Error::VotingClosed);
-        }
+        proposer.require_auth();
 
-        // Record vote
-        if vote_for {
-            proposal.votes_for += 1;
-        } else {
-            proposal.votes_against += 1;
+        let proposal_id = Self::store_proposal(
+            &env,
+            &proposer,
+            title,
+            description,
+            ProposalAction::Clawback,
+            0,
+            &proposer,
+        )?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ClawbackGrant(proposal_id), &grant_id);
+        Ok(proposal_id)
+    }
+
+    /// Create a PolicyChange proposal that sets `param` when executed.
+    pub fn create_policy_proposal(
+        env: Env,
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        param: PolicyParam,
+    ) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &proposer)?;
+
+        proposer.require_auth();
+
+        match &param {
+            PolicyParam::ProposalBond(bond) if *bond < 0 => return Err(Error::InvalidProposal),
+            PolicyParam::ActionRules(_, rules) => Self::validate_rules(rules)?,
+            _ => {}
         }
-        proposal.total_votes += 1;
 
-        // Save vote record (prevents double voting)
-        env.storage().persistent().set(&vote_key, &vote_for);
+        let proposal_id = Self::store_proposal(
+            &env,
+            &proposer,
+            title,
+            description,
+            ProposalAction::PolicyChange,
+            0,
+            &proposer,
+        )?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PolicyParam(proposal_id), &param);
+        Ok(proposal_id)
+    }
 
-        // Save updated proposal
+    /// Create an optimistic proposal for routine changes. It needs no quorum:
+    /// members can only `veto` it, and it passes when its window ends unless
+    /// more than the veto percentage of members vetoed. Passed optimistic
+    /// proposals execute like any other.
+    pub fn create_optimistic_proposal(
+        env: Env,
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        action: ProposalAction,
+        amount: i128,
+        target: Address,
+    ) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &proposer)?;
+
+        proposer.require_auth();
+
+        if action == ProposalAction::ElectSigner {
+            return Err(Error::InvalidProposal);
+        }
+
+        let proposal_id =
+            Self::store_proposal(&env, &proposer, title, description, action, amount, &target)?;
+        let veto_percent = Self::veto_percent(&env);
         env.storage()
             .persistent()
-            .set(&DataKey::Proposal(proposal_id), &proposal);
+            .set(&DataKey::Optimistic(proposal_id), &veto_percent);
 
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("vote")),
-            (proposal_id, voter.clone(), vote_for),
-        );
-
-        log!(
-            &env,
-            "Vote cast on proposal #{}: {:?} voted {}",
-            proposal_id,
-            voter,
-            if vote_for { "FOR" } else { "AGAINST" }
+            (symbol_short!("gov"), symbol_short!("optimist")),
+            (proposal_id, veto_percent),
         );
 
-        Ok(())
+        Ok(proposal_id)
     }
 
-    // ========================================================================
-    // Proposal Finalization
-    // ========================================================================
+    /// Create a multi-choice proposal between named options. Members vote
+    /// with `vote_choice`; the winner is recorded on finalize.
+    ///
+    /// # Arguments
+    /// * `method` - How ballots are counted.
+    /// * `options` - Between 2 and `MAX_CHOICE_OPTIONS` option names.
+    pub fn create_choice_proposal(
+        env: Env,
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        method: TallyMethod,
+        options: Vec<Symbol>,
+    ) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &proposer)?;
 
-    /// Finalize a proposal after the voting period ends.
-    /// Determines if the proposal passed or was rejected based on votes and quorum.
-    pub fn finalize(env: Env, caller: Address, proposal_id: u64) -> Result<ProposalStatus, Error> {
+        proposer.require_auth();
+
+        Self::store_choice_proposal(
+            &env,
+            &proposer,
+            title,
+            description,
+            ProposalAction::General,
+            method,
+            ChoiceOptions::Labels(options),
+        )
+    }
+
+    /// Create an election between signer candidates. When the proposal is
+    /// executed, the winner is added as a treasury signer; the treasury must
+    /// have this contract registered via `propose_governance`.
+    ///
+    /// # Arguments
+    /// * `method` - How ballots are counted.
+    /// * `candidates` - Between 2 and `MAX_CHOICE_OPTIONS` distinct addresses.
+    pub fn create_signer_election(
+        env: Env,
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        method: TallyMethod,
+        candidates: Vec<Address>,
+    ) -> Result<u64, Error> {
         Self::require_initialized(&env)?;
-        Self::require_member(&env, &caller)?;
+        Self::require_member(&env, &proposer)?;
 
-        caller.require_auth();
+        proposer.require_auth();
 
-        let mut proposal: Proposal = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .ok_or(Error::ProposalNotFound)?;
+        let mut seen: Map<Address, ()> = Map::new(&env);
+        for candidate in candidates.iter() {
+            if seen.contains_key(candidate.clone()) {
+                return Err(Error::InvalidProposal);
+            }
+            seen.set(candidate, ());
+        }
 
-        if proposal.status != ProposalStatus::Active {
-            return Err(Error::VotingClosed);
+        Self::store_choice_proposal(
+            &env,
+            &proposer,
+            title,
+            description,
+            ProposalAction::ElectSigner,
+            method,
+            ChoiceOptions::Candidates(candidates),
+        )
+    }
+
+    fn store_choice_proposal(
+        env: &Env,
+        proposer: &Address,
+        title: Symbol,
+        description: Symbol,
+        action: ProposalAction,
+        method: TallyMethod,
+        options: ChoiceOptions,
+    ) -> Result<u64, Error> {
+        let option_count = match &options {
+            ChoiceOptions::Labels(labels) => labels.len(),
+            ChoiceOptions::Candidates(candidates) => candidates.len(),
+        };
+        if !(2..=MAX_CHOICE_OPTIONS).contains(&option_count) {
+            return Err(Error::InvalidProposal);
         }
 
-        // Check voting period has ended
-        let current_ledger = env.ledger().sequence();
-        if current_ledger <= proposal.ends_at {
-            return Err(Error::VotingStillActive);
+        let proposal_id =
+            Self::store_proposal(env, proposer, title, description, action, 0, proposer)?;
+        let mut tallies = Vec::new(env);
+        for _ in 0..option_count {
+            tallies.push_back(0u32);
         }
+        let choice = Choice {
+            method,
+            options,
+            tallies,
+            winner: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Choice(proposal_id), &choice);
+        Ok(proposal_id)
+    }
 
-        // Check quorum
-        let members: Vec<Address> = env
+    /// Allocate an ID and store a new Active proposal, escrowing the current
+    /// proposal bond from the proposer. Callers have already checked and
+    /// authorized the proposer.
+    fn store_proposal(
+        env: &Env,
+        proposer: &Address,
+        title: Symbol,
+        description: Symbol,
+        action: ProposalAction,
+        amount: i128,
+        target: &Address,
+    ) -> Result<u64, Error> {
+        let bond: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(&env));
-        let quorum_percent: u32 = env
+            .get(&DataKey::ProposalBond)
+            .unwrap_or(0);
+        if bond > 0 {
+            let asset = Self::treasury_asset(env)?;
+            token::Client::new(env, &asset).transfer(
+                proposer,
+                &env.current_contract_address(),
+                &bond,
+            );
+        }
+
+        // Get and increment counter
+        let proposal_id: u64 = env
             .storage()
             .instance()
-            .get(&DataKey::QuorumPercent)
-            .unwrap_or(50);
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalCounter, &proposal_id);
 
-        let quorum_threshold = (members.len() * quorum_percent) / 100;
-        if proposal.total_votes < quorum_threshold {
-            proposal.status = ProposalStatus::Expired;
-        } else if proposal.votes_for > proposal.votes_against {
-            proposal.status = ProposalStatus::Passed;
+        // Calculate voting end on the configured clock
+        let rules = Self::action_rules(env, &action);
+        let clock = Self::voting_clock(env);
+        let current_ledger = env.ledger().sequence();
+        let now = env.ledger().timestamp();
+        let review_delay: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReviewDelay)
+            .unwrap_or(0);
+        let voting_starts_at = Self::clock_now(env, clock) + review_delay as u64;
+        let (ends_at, ends_time) = match clock {
+            VotingClock::Ledger => (voting_starts_at as u32 + rules.voting_period, 0),
+            VotingClock::Timestamp => (0, voting_starts_at + rules.voting_period as u64),
+        };
+        let status = if review_delay > 0 {
+            ProposalStatus::Pending
         } else {
-            proposal.status = ProposalStatus::Rejected;
-        }
+            ProposalStatus::Active
+        };
 
-        let final_status = proposal.status.clone();
+        let proposal = Proposal {
+            id: proposal_id,
+            title: title.clone(),
+            description,
+            action: action.clone(),
+            proposer: proposer.clone(),
+            votes_for: 0,
+            votes_against: 0,
+            total_votes: 0,
+            status,
+            created_at: current_ledger,
+            ends_at,
+            amount,
+            target: target.clone(),
+            executable_at: 0,
+            treasury_tx_id: None,
+            bond,
+            rules,
+            clock,
+            created_time: now,
+            ends_time,
+            voting_starts_at,
+            mode: env
+                .storage()
+                .instance()
+                .get(&DataKey::VotingMode)
+                .unwrap_or(VotingMode::OneMemberOneVote),
+            executable_time: 0,
+        };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Proposal(proposal_id), &proposal);
+        Self::save_proposal(env, &proposal);
+        Self::index_status(env, proposal_id, &proposal.status);
 
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("finalize")),
-            (proposal_id, final_status.clone()),
+            (symbol_short!("gov"), symbol_short!("propose")),
+            (proposal_id, proposer.clone(), title, action),
         );
 
-        Ok(final_status)
+        log!(env, "Proposal #{} created by {:?}", proposal_id, proposer);
+        Ok(proposal_id)
     }
 
-    /// Execute a passed proposal.
-    /// Only the admin or proposer can execute.
-    ///
-    /// # Cross-contract execution (Funding proposals)
-    ///
-    /// For `ProposalAction::Funding`, the governance contract calls the
-    /// treasury's `propose_withdrawal()` to queue a withdrawal for the
-    /// approved amount and recipient. This follows the *propose-into-treasury*
-    /// trust model: governance does not directly execute the withdrawal;
-    /// instead it creates a treasury transaction that still requires
-    /// signer approvals according to the treasury's m-of-n policy.
+    // ========================================================================
+    // Voting
+    // ========================================================================
+
+    /// Cast a vote on an active proposal.
     ///
-    /// The governance contract must be registered as a signer in the treasury
-    /// (via `TreasuryContract::add_signer`) for the cross-contract call to
-    /// succeed. If the treasury call fails (e.g. insufficient funds, governance
-    /// not a signer, policy invalidated), the proposal is NOT marked executed
-    /// and the error is propagated to the caller.
+    /// # Arguments
+    /// * `voter` - Must be a DAO member.
+    /// * `proposal_id` - The ID of the proposal to vote on.
+    /// * `vote_for` - `true` to vote in favor, `false` to vote against.
+    pub fn vote(env: Env, voter: Address, proposal_id: u64, vote_for: bool) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &voter)?;
+
+        voter.require_auth();
+
+        Self::cast_vote(&env, &voter, proposal_id, vote_for, VoteReason::None)
+    }
+
+    /// Cast a vote with a reason, stored in the vote record (see `get_vote`)
+    /// and published in the vote event.
     ///
-    /// This preserves treasury sovereignty — the governance DAO can approve
-    /// spending but cannot unilaterally move funds without going through the
-    /// treasury's own multi-sig approval process.
-    pub fn execute_proposal(
+    /// # Errors
+    /// * `Error::ReasonTooLong` - If a text reason exceeds `MAX_REASON_LEN` bytes.
+    pub fn vote_with_reason(
         env: Env,
-        executor: Address,
+        voter: Address,
         proposal_id: u64,
+        vote_for: bool,
+        reason: VoteReason,
     ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_member(&env, &voter)?;
 
-        executor.require_auth();
-
-        let mut proposal: Proposal = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .ok_or(Error::ProposalNotFound)?;
-
-        if proposal.status != ProposalStatus::Passed {
-            return Err(Error::ProposalRejected);
-        }
-
-        // Only admin or proposer can execute
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        if executor != proposal.proposer && executor != admin {
-            return Err(Error::Unauthorized);
-        }
-
-        if executor == admin {
-            Self::require_acl_admin_or_above(&env, &executor)?;
-        } else if executor == proposal.proposer {
-            Self::require_acl_member_or_above(&env, &executor)?;
-        }
+        voter.require_auth();
 
-        // Handle member add/remove actions
-        match proposal.action {
-            ProposalAction::AddMember => {
-                Self::internal_add_member(&env, &proposal.target)?;
-            }
-            ProposalAction::RemoveMember => {
-                Self::internal_remove_member(&env, &proposal.target)?;
+        if let VoteReason::Text(text) = &reason {
+            if text.len() > MAX_REASON_LEN {
+                return Err(Error::ReasonTooLong);
             }
-            ProposalAction::Funding => {
-                let treasury_address: Address = env
-                    .storage()
-                    .instance()
-                    .get(&DataKey::TreasuryAddress)
-                    .ok_or(Error::NotInitialized)?;
+        }
+        Self::cast_vote(&env, &voter, proposal_id, vote_for, reason)
+    }
 
-                let treasury_client =
-                    TreasuryContractClient::new(&env, &treasury_address);
+    /// Cast a vote signed by the voter's registered vote key, so a relayer
+    /// can pay the fee. The vote is recorded exactly as `vote` records it.
+    ///
+    /// # Arguments
+    /// * `nonce` - The voter's next nonce; it is consumed by the vote.
+    /// * `signature` - ed25519 signature over `vote_payload(voter,
+    ///   proposal_id, vote_for, nonce)`.
+    ///
+    /// # Errors
+    /// * `Error::VoteKeyNotSet` - If the voter has no vote key.
+    /// * `Error::InvalidNonce` - If `nonce` is not the voter's next nonce.
+    ///
+    /// # Panics
+    /// If the signature does not verify.
+    pub fn vote_by_sig(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        vote_for: bool,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &voter)?;
 
-                let expires_at = env
-                    .ledger()
-                    .timestamp()
-                    .checked_add(7 * 24 * 3600)
-                    .unwrap_or(u64::MAX);
+        Self::verify_signed_vote(&env, &voter, proposal_id, vote_for, nonce, &signature)?;
+        Self::cast_vote(&env, &voter, proposal_id, vote_for, VoteReason::None)
+    }
 
-                let memo = symbol_short!("gov_fund");
+    /// Cast a relayer's batch of signed votes in one invocation. A vote
+    /// that `vote_by_sig` would reject with an error (the voter is not a
+    /// member, already voted or has moved past the nonce, the proposal is
+    /// not open, ...) is skipped without consuming its nonce, so one voter
+    /// cannot void the rest of the batch by voting directly.
+    ///
+    /// A signature that fails to verify still aborts the whole call: the
+    /// host traps on it. Relayers should check each signature against
+    /// `vote_payload` before batching it.
+    ///
+    /// # Returns
+    /// Positions in `votes` of the skipped votes; every other vote was cast.
+    pub fn vote_by_sig_batch(env: Env, votes: Vec<SignedVote>) -> Result<Vec<u32>, Error> {
+        Self::require_initialized(&env)?;
 
-                let _ = treasury_client
-                    .try_propose_withdrawal(
-                        &env.current_contract_address(),
-                        &proposal.target,
-                        &proposal.amount,
-                        &memo,
-                        &expires_at,
+        let mut skipped = Vec::new(&env);
+        for (index, vote) in votes.iter().enumerate() {
+            let cast = Self::require_member(&env, &vote.voter)
+                .and_then(|_| {
+                    Self::verify_signed_vote(
+                        &env,
+                        &vote.voter,
+                        vote.proposal_id,
+                        vote.vote_for,
+                        vote.nonce,
+                        &vote.signature,
                     )
-                    .map_err(|_| Error::ProposalRejected)?;
-            }
-            ProposalAction::PolicyChange | ProposalAction::General => {
-                // PolicyChange and General proposals are handled externally
+                })
+                .and_then(|_| {
+                    Self::cast_vote(
+                        &env,
+                        &vote.voter,
+                        vote.proposal_id,
+                        vote.vote_for,
+                        VoteReason::None,
+                    )
+                });
+            if cast.is_err() {
+                skipped.push_back(index as u32);
             }
         }
+        Ok(skipped)
+    }
+
+    /// Register the ed25519 public key `member` signs relayed votes with,
+    /// replacing any earlier key.
+    pub fn register_vote_key(
+        env: Env,
+        member: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &member)?;
+
+        member.require_auth();
 
-        proposal.status = ProposalStatus::Executed;
         env.storage()
             .persistent()
-            .set(&DataKey::Proposal(proposal_id), &proposal);
+            .set(&DataKey::VoteKey(member.clone()), &public_key);
 
         env.events().publish(
-            (symbol_short!("gov"), symbol_short!("exec")),
-            (proposal_id, executor.clone()),
+            (symbol_short!("gov"), symbol_short!("vote_key")),
+            (member, public_key),
         );
 
         Ok(())
     }
 
-    // ========================================================================
-    // Member Management (Internal)
-    // ========================================================================
+    /// The bytes a voter signs for `vote_by_sig`: the XDR of
+    /// `("gov_vote", network_id, contract, voter, proposal_id, vote_for, nonce)`.
+    /// The network and contract make a signature useless anywhere else.
+    pub fn vote_payload(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        vote_for: bool,
+        nonce: u64,
+    ) -> Bytes {
+        (
+            symbol_short!("gov_vote"),
+            env.ledger().network_id(),
+            env.current_contract_address(),
+            voter,
+            proposal_id,
+            vote_for,
+            nonce,
+        )
+            .to_xdr(&env)
+    }
 
-    fn internal_add_member(env: &Env, member: &Address) -> Result<(), Error> {
-        let mut members: Vec<Address> = env
+    /// Check a signed vote and consume its nonce. Everything `cast_vote`
+    /// checks is checked first, so a vote that cannot be cast never reaches
+    /// signature verification or consumes the nonce.
+    fn verify_signed_vote(
+        env: &Env,
+        voter: &Address,
+        proposal_id: u64,
+        vote_for: bool,
+        nonce: u64,
+        signature: &BytesN<64>,
+    ) -> Result<(), Error> {
+        let public_key: BytesN<32> = env
             .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(env));
-
-        // Check not already a member
-        for i in 0..members.len() {
-            if members.get(i).unwrap() == *member {
-                return Ok(()); // Already a member, no-op
-            }
+            .persistent()
+            .get(&DataKey::VoteKey(voter.clone()))
+            .ok_or(Error::VoteKeyNotSet)?;
+        let nonce_key = DataKey::VoteNonce(voter.clone());
+        let expected: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+        if nonce != expected {
+            return Err(Error::InvalidNonce);
         }
+        Self::yes_no_proposal(env, proposal_id, voter)?;
 
-        members.push_back(member.clone());
-        env.storage().instance().set(&DataKey::Members, &members);
+        let payload = Self::vote_payload(env.clone(), voter.clone(), proposal_id, vote_for, nonce);
+        env.crypto()
+            .ed25519_verify(&public_key, &payload, signature);
+
+        env.storage().persistent().set(&nonce_key, &(expected + 1));
         Ok(())
     }
 
-    fn internal_remove_member(env: &Env, member: &Address) -> Result<(), Error> {
-        let members: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(env));
+    /// Record a yes/no vote from an authorized member.
+    fn cast_vote(
+        env: &Env,
+        voter: &Address,
+        proposal_id: u64,
+        vote_for: bool,
+        reason: VoteReason,
+    ) -> Result<(), Error> {
+        let mut proposal = Self::yes_no_proposal(env, proposal_id, voter)?;
 
-        let mut new_members = Vec::new(env);
-        for i in 0..members.len() {
-            let m = members.get(i).unwrap();
-            if m != *member {
-                new_members.push_back(m);
-            }
-        }
+        Self::record_vote(env, &mut proposal, voter, vote_for, 1, reason.clone())?;
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("vote")),
+            (proposal_id, voter.clone(), vote_for, reason),
+        );
+
+        log!(
+            env,
+            "Vote cast on proposal #{}: {:?} voted {}",
+            proposal_id,
+            voter,
+            if vote_for { "FOR" } else { "AGAINST" }
+        );
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Members, &new_members);
         Ok(())
     }
 
-    // ========================================================================
-    // Query Functions
-    // ========================================================================
+    /// Cast a quadratic vote, spending `credits` of the voter's voice
+    /// credits for this period. The vote weighs `isqrt(credits)`; quorum
+    /// still counts one vote per member.
+    ///
+    /// # Errors
+    /// * `Error::InvalidBallot` - If the proposal is not in `Quadratic` mode.
+    /// * `Error::InvalidCredits` - If `credits` is zero.
+    /// * `Error::InsufficientCredits` - If the voter has too few credits left.
+    pub fn vote_quadratic(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        vote_for: bool,
+        credits: u64,
+    ) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &voter)?;
 
-    /// Get a proposal by its ID.
-    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, Error> {
+        voter.require_auth();
+
+        let mut proposal = Self::open_proposal_for(&env, proposal_id, &voter)?;
+        if Self::has_special_ballot(&env, proposal_id) || proposal.mode != VotingMode::Quadratic {
+            return Err(Error::InvalidBallot);
+        }
+        if credits == 0 {
+            return Err(Error::InvalidCredits);
+        }
+
+        let (mut usage, allotment) = Self::credit_usage(&env, &voter);
+        let spent = usage
+            .spent
+            .checked_add(credits)
+            .ok_or(Error::InsufficientCredits)?;
+        if spent > allotment {
+            return Err(Error::InsufficientCredits);
+        }
+        usage.spent = spent;
         env.storage()
             .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .ok_or(Error::ProposalNotFound)
+            .set(&DataKey::CreditUsage(voter.clone()), &usage);
+
+        // isqrt(u64::MAX) < 2^32, so the weight always fits
+        let weight = isqrt(credits) as u32;
+        Self::record_vote(
+            &env,
+            &mut proposal,
+            &voter,
+            vote_for,
+            weight,
+            VoteReason::None,
+        )?;
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("qvote")),
+            (proposal_id, voter, vote_for, credits, weight),
+        );
+
+        Ok(weight)
     }
 
-    /// Get governance configuration.
-    pub fn get_config(env: Env) -> Result<GovConfig, Error> {
+    /// Veto an optimistic proposal. Each member's veto counts once, in
+    /// `votes_against`.
+    ///
+    /// # Returns
+    /// The number of vetoes so far.
+    pub fn veto(env: Env, voter: Address, proposal_id: u64) -> Result<u32, Error> {
         Self::require_initialized(&env)?;
+        Self::require_member(&env, &voter)?;
 
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        let members: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(&env));
-        let quorum_percent: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::QuorumPercent)
-            .unwrap_or(50);
-        let voting_period: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::VotingPeriod)
-            .unwrap_or(1000);
-        let proposal_count: u64 = env
+        voter.require_auth();
+
+        let mut proposal = Self::open_proposal_for(&env, proposal_id, &voter)?;
+        if !env
             .storage()
-            .instance()
-            .get(&DataKey::ProposalCounter)
-            .unwrap_or(0);
+            .persistent()
+            .has(&DataKey::Optimistic(proposal_id))
+        {
+            return Err(Error::InvalidBallot);
+        }
+        Self::record_vote(&env, &mut proposal, &voter, false, 1, VoteReason::None)?;
 
-        Ok(GovConfig {
-            admin,
-            member_count: members.len(),
-            quorum_percent,
-            voting_period,
-            proposal_count,
-        })
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("veto")),
+            (proposal_id, voter, proposal.votes_against),
+        );
+
+        Ok(proposal.votes_against)
     }
 
-    /// Get the list of DAO members.
-    pub fn get_members(env: Env) -> Vec<Address> {
+    /// Add a yes/no vote of `weight` and save the proposal.
+    fn record_vote(
+        env: &Env,
+        proposal: &mut Proposal,
+        voter: &Address,
+        vote_for: bool,
+        weight: u32,
+        reason: VoteReason,
+    ) -> Result<(), Error> {
+        if vote_for {
+            proposal.votes_for = proposal
+                .votes_for
+                .checked_add(weight)
+                .ok_or(Error::InvalidBallot)?;
+        } else {
+            proposal.votes_against = proposal
+                .votes_against
+                .checked_add(weight)
+                .ok_or(Error::InvalidBallot)?;
+        }
+        proposal.total_votes += 1;
+        Self::index_vote(env, proposal.id, proposal.total_votes - 1, voter);
+
+        // Save vote record (prevents double voting)
+        let record = VoteRecord {
+            vote_for,
+            choices: Vec::new(env),
+            weight,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        };
         env.storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(&env))
+            .persistent()
+            .set(&DataKey::Vote(proposal.id, voter.clone()), &record);
+
+        if vote_for {
+            Self::open_yes_vote(env, proposal.id, voter);
+        }
+
+        // Save updated proposal
+        Self::save_proposal(env, proposal);
+        Ok(())
     }
 
-    /// Check if an address has voted on a proposal.
-    pub fn has_voted(env: Env, proposal_id: u64, voter: Address) -> bool {
+    /// Cast a ballot on a multi-choice proposal.
+    ///
+    /// # Arguments
+    /// * `voter` - Must be a DAO member.
+    /// * `proposal_id` - The ID of the proposal to vote on.
+    /// * `choices` - Option indexes: exactly one under `Plurality`, the
+    ///   approved options under `Approval`, or options in order of preference
+    ///   under `InstantRunoff` (unranked options are never preferred).
+    pub fn vote_choice(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        choices: Vec<u32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &voter)?;
+
+        voter.require_auth();
+
+        let mut proposal = Self::open_proposal_for(&env, proposal_id, &voter)?;
+        let mut choice: Choice = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Choice(proposal_id))
+            .ok_or(Error::InvalidBallot)?;
+
+        if choices.is_empty() || (choice.method == TallyMethod::Plurality && choices.len() != 1) {
+            return Err(Error::InvalidBallot);
+        }
+        for i in 0..choices.len() {
+            let option = choices.get(i).unwrap();
+            if option >= choice.tallies.len() {
+                return Err(Error::InvalidBallot);
+            }
+            for j in (i + 1)..choices.len() {
+                if choices.get(j).unwrap() == option {
+                    return Err(Error::InvalidBallot);
+                }
+            }
+        }
+
+        // Running tallies: every approval, or the first preference
+        let counted = if choice.method == TallyMethod::Approval {
+            choices.len()
+        } else {
+            1
+        };
+        for option in choices.iter().take(counted as usize) {
+            let tally = choice.tallies.get(option).unwrap();
+            choice.tallies.set(option, tally + 1);
+        }
+        if choice.method == TallyMethod::InstantRunoff {
+            env.storage().persistent().set(
+                &DataKey::BallotAt(proposal_id, proposal.total_votes),
+                &choices,
+            );
+        }
+        proposal.total_votes += 1;
+        Self::index_vote(&env, proposal_id, proposal.total_votes - 1, &voter);
+
+        let record = VoteRecord {
+            vote_for: true,
+            choices: choices.clone(),
+            weight: 1,
+            reason: VoteReason::None,
+            timestamp: env.ledger().timestamp(),
+        };
         env.storage()
             .persistent()
-            .has(&DataKey::Vote(proposal_id, voter))
+            .set(&DataKey::Vote(proposal_id, voter.clone()), &record);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Choice(proposal_id), &choice);
+        Self::save_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("ballot")),
+            (proposal_id, voter, choices),
+        );
+
+        Ok(())
+    }
+
+    /// Load a proposal `voter` may still cast a one-member-one-vote yes/no
+    /// vote on.
+    fn yes_no_proposal(env: &Env, proposal_id: u64, voter: &Address) -> Result<Proposal, Error> {
+        let proposal = Self::open_proposal_for(env, proposal_id, voter)?;
+        if Self::has_special_ballot(env, proposal_id)
+            || proposal.mode != VotingMode::OneMemberOneVote
+        {
+            return Err(Error::InvalidBallot);
+        }
+        Ok(proposal)
+    }
+
+    /// Load a proposal `voter` may still vote on.
+    fn open_proposal_for(env: &Env, proposal_id: u64, voter: &Address) -> Result<Proposal, Error> {
+        // Check if already voted
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Vote(proposal_id, voter.clone()))
+        {
+            return Err(Error::AlreadyVoted);
+        }
+
+        let mut proposal = Self::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+
+        // Check proposal is out of review and still active
+        Self::open_voting(env, &mut proposal);
+        if proposal.status == ProposalStatus::Pending {
+            return Err(Error::VotingNotStarted);
+        }
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::VotingClosed);
+        }
+
+        // Check voting period hasn't ended
+        if Self::voting_ended(env, &proposal) {
+            return Err(Error::VotingClosed);
+        }
+        Ok(proposal)
     }
 
     // ========================================================================
-    // Admin Functions
+    // Proposal Finalization
     // ========================================================================
 
-    /// Transfer admin role to a new address.
-    pub fn transfer_admin(
+    /// Finalize a proposal after the voting period ends.
+    /// Determines if the proposal passed or was rejected based on votes and quorum.
+    ///
+    /// A passed proposal's bond is returned to the proposer; a rejected or
+    /// expired (quorum not met) proposal's bond is slashed into the treasury.
+    ///
+    /// An optimistic proposal needs no quorum: it passes unless its vetoes
+    /// exceed its veto percentage of members, and is then `Vetoed`. It is
+    /// left out of `get_quorum_stats`.
+    ///
+    /// A multi-choice proposal that meets quorum records its winner and
+    /// passes; a tie for first place, or no ballots, rejects it. Instant-runoff
+    /// ties for last place eliminate the higher option index.
+    pub fn finalize(env: Env, caller: Address, proposal_id: u64) -> Result<ProposalStatus, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &caller)?;
+
+        caller.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+
+        Self::open_voting(&env, &mut proposal);
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::VotingClosed);
+        }
+
+        // Check voting period has ended, on the clock the proposal was created with
+        if !Self::voting_ended(&env, &proposal) {
+            return Err(Error::VotingStillActive);
+        }
+        if let Some(veto_percent) = env
+            .storage()
+            .persistent()
+            .get::<_, u32>(&DataKey::Optimistic(proposal_id))
+        {
+            return Self::finalize_optimistic(&env, &mut proposal, veto_percent);
+        }
+
+        // Check quorum and approval against the rules fixed at creation
+        let quorum_threshold = Self::quorum_threshold(&env, proposal.rules.quorum_percent);
+        let decisive_votes = (proposal.votes_for + proposal.votes_against) as u64;
+        let choice: Option<Choice> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Choice(proposal_id));
+        let approved = match choice {
+            Some(mut choice) if proposal.total_votes >= quorum_threshold => {
+                choice.winner = Self::choice_winner(&env, &proposal, &choice);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Choice(proposal_id), &choice);
+                if let Some(winner) = choice.winner {
+                    env.events().publish(
+                        (symbol_short!("gov"), symbol_short!("winner")),
+                        (proposal_id, winner),
+                    );
+                }
+                choice.winner.is_some()
+            }
+            Some(_) => false,
+            None => {
+                proposal.votes_for > proposal.votes_against
+                    && proposal.votes_for as u64 * 100
+                        >= proposal.rules.approval_percent as u64 * decisive_votes
+            }
+        };
+        if proposal.total_votes < quorum_threshold {
+            Self::set_status(&env, &mut proposal, ProposalStatus::Expired);
+        } else if approved {
+            Self::set_status(&env, &mut proposal, ProposalStatus::Passed);
+            Self::start_timelock(&env, &mut proposal);
+        } else {
+            Self::set_status(&env, &mut proposal, ProposalStatus::Rejected);
+        }
+
+        let final_status = proposal.status.clone();
+        Self::settle_bond(&env, &proposal, final_status == ProposalStatus::Passed)?;
+
+        let mut stats = Self::quorum_stats(&env);
+        stats.finalized += 1;
+        if final_status != ProposalStatus::Expired {
+            stats.quorum_reached += 1;
+        }
+        stats.votes_cast += proposal.total_votes as u64;
+        stats.eligible_votes += Self::get_members(env.clone()).len() as u64;
+        env.storage().instance().set(&DataKey::QuorumStats, &stats);
+
+        Self::save_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("finalize")),
+            (proposal_id, final_status.clone()),
+        );
+
+        Ok(final_status)
+    }
+
+    /// Settle an optimistic proposal whose window has ended and save it.
+    fn finalize_optimistic(
+        env: &Env,
+        proposal: &mut Proposal,
+        veto_percent: u32,
+    ) -> Result<ProposalStatus, Error> {
+        let member_count = Self::get_members(env.clone()).len() as u64;
+        if proposal.votes_against as u64 * 100 > veto_percent as u64 * member_count {
+            Self::set_status(env, proposal, ProposalStatus::Vetoed);
+        } else {
+            Self::set_status(env, proposal, ProposalStatus::Passed);
+            Self::start_timelock(env, proposal);
+        }
+
+        let final_status = proposal.status.clone();
+        Self::settle_bond(env, proposal, final_status == ProposalStatus::Passed)?;
+        Self::save_proposal(env, proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("finalize")),
+            (proposal.id, final_status.clone()),
+        );
+
+        Ok(final_status)
+    }
+
+    /// Cancel a Pending or Active proposal.
+    ///
+    /// The proposer may withdraw their proposal until it reaches quorum, and
+    /// gets its bond back. The admin may cancel any such proposal, giving a
+    /// `reason`; the bond is then slashed into the treasury. Canceled
+    /// proposals are excluded from `get_quorum_stats`.
+    ///
+    /// # Errors
+    /// * `Error::VotingClosed` - If the proposal is no longer Pending or
+    ///   Active, or its voting period has ended; it can only be finalized.
+    /// * `Error::CancelNotAllowed` - If the proposer cancels after quorum.
+    /// * `Error::Unauthorized` - If the caller is neither proposer nor admin.
+    pub fn cancel_proposal(
         env: Env,
-        current_admin: Address,
-        new_admin: Address,
+        caller: Address,
+        proposal_id: u64,
+        reason: Symbol,
     ) -> Result<(), Error> {
         Self::require_initialized(&env)?;
-        Self::require_admin(&env, &current_admin)?;
 
-        current_admin.require_auth();
+        caller.require_auth();
 
-        Self::require_acl_admin_or_above(&env, &new_admin)?;
+        let mut proposal = Self::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+        if !matches!(
+            proposal.status,
+            ProposalStatus::Pending | ProposalStatus::Active
+        ) || Self::voting_ended(&env, &proposal)
+        {
+            return Err(Error::VotingClosed);
+        }
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        let quorum_reached = proposal.total_votes > 0
+            && proposal.total_votes >= Self::quorum_threshold(&env, proposal.rules.quorum_percent);
+        let withdrawal = caller == proposal.proposer && !quorum_reached;
+        if !withdrawal {
+            if caller == admin {
+                Self::require_acl_admin_or_above(&env, &caller)?;
+            } else if caller == proposal.proposer {
+                return Err(Error::CancelNotAllowed);
+            } else {
+                return Err(Error::Unauthorized);
+            }
+        }
+
+        Self::set_status(&env, &mut proposal, ProposalStatus::Canceled);
+        Self::settle_bond(&env, &proposal, withdrawal)?;
+        Self::save_proposal(&env, &proposal);
+
+        let mut stats = Self::quorum_stats(&env);
+        stats.canceled += 1;
+        env.storage().instance().set(&DataKey::QuorumStats, &stats);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("cancel")),
+            (proposal_id, caller.clone(), reason),
+        );
+
+        Ok(())
+    }
+
+    /// Execute a passed proposal once its timelock (`executable_at`, or
+    /// `executable_time` under the Timestamp clock) has passed.
+    /// Only the admin or proposer can execute.
+    ///
+    /// # Cross-contract execution (Funding proposals)
+    ///
+    /// For `ProposalAction::Funding`, the governance contract calls the
+    /// treasury's `propose_withdrawal()` to queue a withdrawal for the
+    /// approved amount and recipient. This follows the *propose-into-treasury*
+    /// trust model: governance does not directly execute the withdrawal;
+    /// instead it creates a treasury transaction that still requires
+    /// signer approvals according to the treasury's m-of-n policy.
+    ///
+    /// The governance contract must be registered as a signer in the treasury
+    /// (via `TreasuryContract::add_signer`) for the cross-contract call to
+    /// succeed. If the treasury call fails (e.g. insufficient funds, governance
+    /// not a signer, policy invalidated), the proposal is NOT marked executed
+    /// and the error is propagated to the caller.
+    ///
+    /// The treasury transaction ID is recorded in `treasury_tx_id`; see
+    /// `sync_funding_status` for its outcome. A `FundingFailed` proposal can
+    /// be executed again to queue a fresh treasury transaction.
+    ///
+    /// A proposal from `create_funding_proposal` uses its `FundingRequest` for
+    /// the recipient, expiry and memo, and fails with `AssetMismatch` if the
+    /// requested asset is not the treasury's.
+    ///
+    /// This preserves treasury sovereignty — the governance DAO can approve
+    /// spending but cannot unilaterally move funds without going through the
+    /// treasury's own multi-sig approval process.
+    ///
+    /// The treasury signers can opt into trusting governance with
+    /// `propose_governance_trust`: governance's approval then counts as
+    /// several signers' approvals, or meets the threshold on its own for
+    /// amounts up to a limit, so the queued transaction may be executable
+    /// without further approvals.
+    ///
+    /// A Funding proposal created with `create_grant_proposal` calls the
+    /// treasury's `propose_grant()` instead, so the approved amount is
+    /// escrowed into milestones. A `Clawback` proposal calls the treasury's
+    /// `clawback_grant()`, which requires this contract to be registered via
+    /// `propose_governance`, as does an `ElectSigner` proposal, which calls the
+    /// treasury's `add_signer()` with the winning candidate.
+    ///
+    /// An optimistic proposal whose window has ended unvetoed can be executed
+    /// without a separate `finalize` when there is no execution delay.
+    pub fn execute_proposal(env: Env, executor: Address, proposal_id: u64) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        executor.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+
+        Self::open_voting(&env, &mut proposal);
+        if proposal.status == ProposalStatus::Active && Self::voting_ended(&env, &proposal) {
+            if let Some(veto_percent) = env
+                .storage()
+                .persistent()
+                .get::<_, u32>(&DataKey::Optimistic(proposal_id))
+            {
+                Self::finalize_optimistic(&env, &mut proposal, veto_percent)?;
+            }
+        }
+        if !matches!(
+            proposal.status,
+            ProposalStatus::Passed | ProposalStatus::FundingFailed
+        ) {
+            return Err(Error::ProposalRejected);
+        }
+        if !Self::timelock_elapsed(&env, &proposal) {
+            return Err(Error::TimelockActive);
+        }
+
+        // Only admin or proposer can execute
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if executor != proposal.proposer && executor != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if executor == admin {
+            Self::require_acl_admin_or_above(&env, &executor)?;
+        } else if executor == proposal.proposer {
+            Self::require_acl_member_or_above(&env, &executor)?;
+        }
+
+        // Handle member add/remove actions
+        match proposal.action {
+            ProposalAction::AddMember => {
+                Self::internal_add_member(&env, &proposal.target)?;
+            }
+            ProposalAction::RemoveMember => {
+                Self::internal_remove_member(&env, &proposal.target)?;
+            }
+            ProposalAction::Funding => {
+                let treasury_address: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::TreasuryAddress)
+                    .ok_or(Error::NotInitialized)?;
+
+                let treasury_client = TreasuryContractClient::new(&env, &treasury_address);
+
+                // Explicit request parameters, or the legacy defaults.
+                let request: Option<FundingRequest> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::FundingRequest(proposal_id));
+                let (recipient, expires_after, memo) = match request {
+                    Some(request) => {
+                        let treasury_asset = treasury_client
+                            .try_get_config()
+                            .map_err(|_| Error::ProposalRejected)?
+                            .map_err(|_| Error::ProposalRejected)?
+                            .asset;
+                        if request.asset != treasury_asset {
+                            return Err(Error::AssetMismatch);
+                        }
+                        (request.recipient, request.expires_after, request.memo)
+                    }
+                    None => (
+                        proposal.target.clone(),
+                        7 * 24 * 3600,
+                        symbol_short!("gov_fund"),
+                    ),
+                };
+                let expires_at = env
+                    .ledger()
+                    .timestamp()
+                    .checked_add(expires_after)
+                    .unwrap_or(u64::MAX);
+
+                let grant_terms: Option<GrantTerms> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::GrantTerms(proposal_id));
+                let tx_id = match grant_terms {
+                    Some(terms) => treasury_client
+                        .try_propose_grant(
+                            &env.current_contract_address(),
+                            &recipient,
+                            &terms,
+                            &memo,
+                            &expires_at,
+                        )
+                        .map_err(|_| Error::ProposalRejected)?
+                        .map_err(|_| Error::ProposalRejected)?,
+                    None => treasury_client
+                        .try_propose_withdrawal(
+                            &env.current_contract_address(),
+                            &recipient,
+                            &proposal.amount,
+                            &memo,
+                            &expires_at,
+                        )
+                        .map_err(|_| Error::ProposalRejected)?
+                        .map_err(|_| Error::ProposalRejected)?,
+                };
+                proposal.treasury_tx_id = Some(tx_id);
+
+                env.events().publish(
+                    (symbol_short!("gov"), symbol_short!("fund_tx")),
+                    (proposal_id, tx_id),
+                );
+            }
+            ProposalAction::Clawback => {
+                let grant_id: u64 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::ClawbackGrant(proposal_id))
+                    .ok_or(Error::InvalidProposal)?;
+                let treasury_address: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::TreasuryAddress)
+                    .ok_or(Error::NotInitialized)?;
+                TreasuryContractClient::new(&env, &treasury_address)
+                    .try_clawback_grant(&env.current_contract_address(), &grant_id)
+                    .map_err(|_| Error::ClawbackFailed)?
+                    .map_err(|_| Error::ClawbackFailed)?;
+            }
+            ProposalAction::PolicyChange => {
+                // Proposals from create_policy_proposal set a parameter;
+                // others are handled externally
+                let param: Option<PolicyParam> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::PolicyParam(proposal_id));
+                if let Some(param) = param {
+                    match &param {
+                        PolicyParam::ProposalBond(bond) => {
+                            env.storage().instance().set(&DataKey::ProposalBond, bond);
+                        }
+                        PolicyParam::ActionRules(action, rules) => {
+                            env.storage()
+                                .instance()
+                                .set(&DataKey::ActionRules(action.clone()), rules);
+                        }
+                    }
+                    env.events().publish(
+                        (symbol_short!("gov"), symbol_short!("policy")),
+                        (proposal_id, param),
+                    );
+                }
+            }
+            ProposalAction::ElectSigner => {
+                let choice: Choice = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Choice(proposal_id))
+                    .ok_or(Error::InvalidProposal)?;
+                let candidate = match (&choice.options, choice.winner) {
+                    (ChoiceOptions::Candidates(candidates), Some(winner)) => {
+                        candidates.get(winner).ok_or(Error::InvalidProposal)?
+                    }
+                    _ => return Err(Error::InvalidProposal),
+                };
+                let treasury_address: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::TreasuryAddress)
+                    .ok_or(Error::NotInitialized)?;
+                TreasuryContractClient::new(&env, &treasury_address)
+                    .try_add_signer(&env.current_contract_address(), &candidate)
+                    .map_err(|_| Error::ElectionFailed)?
+                    .map_err(|_| Error::ElectionFailed)?;
+            }
+            ProposalAction::General => {
+                // General proposals are handled externally
+            }
+        }
+
+        Self::set_status(&env, &mut proposal, ProposalStatus::Executed);
+        Self::save_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("exec")),
+            (proposal_id, executor.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Record the outcome of an executed Funding proposal's treasury
+    /// transaction: `Funded` once it is executed, `FundingFailed` once it is
+    /// canceled or expired. A `FundingFailed` proposal can be executed again,
+    /// which queues a new treasury transaction. Callable by anyone.
+    ///
+    /// # Returns
+    /// The proposal's status after syncing; `Executed` while the treasury
+    /// transaction is still open.
+    pub fn sync_funding_status(env: Env, proposal_id: u64) -> Result<ProposalStatus, Error> {
+        Self::require_initialized(&env)?;
+
+        let mut proposal = Self::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+        let tx_id = match proposal.treasury_tx_id {
+            Some(tx_id) if proposal.status == ProposalStatus::Executed => tx_id,
+            _ => return Err(Error::FundingNotPending),
+        };
+
+        let treasury_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .ok_or(Error::NotInitialized)?;
+        let tx_status = TreasuryContractClient::new(&env, &treasury_address)
+            .try_get_transaction_status(&tx_id)
+            .map_err(|_| Error::FundingNotPending)?
+            .map_err(|_| Error::FundingNotPending)?;
+
+        // An invalidated transaction can still be revalidated by the signers.
+        let status = match tx_status {
+            TransactionStatus::Executed => ProposalStatus::Funded,
+            TransactionStatus::Canceled | TransactionStatus::Expired => {
+                ProposalStatus::FundingFailed
+            }
+            TransactionStatus::Pending
+            | TransactionStatus::Approved
+            | TransactionStatus::Invalidated => {
+                return Ok(ProposalStatus::Executed);
+            }
+        };
+        Self::set_status(&env, &mut proposal, status);
+        Self::save_proposal(&env, &proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("fund_st")),
+            (proposal_id, tx_id, proposal.status.clone()),
+        );
+
+        Ok(proposal.status)
+    }
+
+    // ========================================================================
+    // Ragequit
+    // ========================================================================
+
+    /// Exit with a pro-rata share of the treasury while a passed Funding
+    /// proposal is in its timelock.
+    ///
+    /// Burns `shares` of the member's treasury shares and transfers the
+    /// matching slice of the treasury balance to them. A member who voted For
+    /// any proposal that is still being voted on, or any Funding proposal
+    /// that is Passed (not yet executed) or FundingFailed (re-executable),
+    /// cannot ragequit. The treasury must have
+    /// this contract registered via `propose_governance`.
+    ///
+    /// # Returns
+    /// The amount paid out by the treasury.
+    pub fn ragequit(
+        env: Env,
+        member: Address,
+        proposal_id: u64,
+        shares: i128,
+    ) -> Result<i128, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &member)?;
+
+        member.require_auth();
+
+        let proposal = Self::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+        if proposal.action != ProposalAction::Funding
+            || proposal.status != ProposalStatus::Passed
+            || Self::timelock_elapsed(&env, &proposal)
+        {
+            return Err(Error::RagequitWindowClosed);
+        }
+
+        // Block on yes votes for proposals that have not settled.
+        let open: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OpenYesVotes(member.clone()))
+            .unwrap_or(0);
+        if open > 0 {
+            return Err(Error::VotedForPendingProposal);
+        }
+        let yes_key = DataKey::YesVotes(member.clone());
+        let legacy: Option<Vec<u64>> = env.storage().persistent().get(&yes_key);
+        if let Some(legacy) = legacy {
+            for id in legacy.iter() {
+                if let Some(voted) = Self::load_proposal(&env, id) {
+                    if Self::blocks_ragequit(&voted.action, &voted.status) {
+                        return Err(Error::VotedForPendingProposal);
+                    }
+                }
+            }
+            env.storage().persistent().remove(&yes_key);
+        }
+
+        let treasury_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .ok_or(Error::NotInitialized)?;
+        let amount = TreasuryContractClient::new(&env, &treasury_address)
+            .try_ragequit(&env.current_contract_address(), &member, &shares)
+            .map_err(|_| Error::RagequitFailed)?
+            .map_err(|_| Error::RagequitFailed)?;
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("ragequit")),
+            (proposal_id, member.clone(), shares, amount),
+        );
+
+        Ok(amount)
+    }
+
+    // ========================================================================
+    // Conviction Voting
+    // ========================================================================
+
+    /// Open a funding request decided by conviction voting. Members stake
+    /// support points on it with `stake_support`; once its conviction
+    /// reaches the threshold, `check_conviction` passes it.
+    pub fn create_conviction_request(
+        env: Env,
+        proposer: Address,
+        recipient: Address,
+        amount: i128,
+        memo: Symbol,
+    ) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &proposer)?;
+
+        proposer.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidProposal);
+        }
+
+        let request_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConvictionCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::ConvictionCounter, &request_id);
+
+        let request = ConvictionRequest {
+            id: request_id,
+            proposer: proposer.clone(),
+            recipient,
+            amount,
+            memo,
+            support: 0,
+            conviction: 0,
+            updated_at: env.ledger().sequence(),
+            status: ConvictionStatus::Open,
+            treasury_tx_id: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ConvictionRequest(request_id), &request);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("cv_req")),
+            (request_id, proposer, amount),
+        );
+
+        Ok(request_id)
+    }
+
+    /// Set the support points `member` stakes on a request, replacing any
+    /// earlier stake. Conviction accrued so far is kept; from now on it moves
+    /// toward the new support. Stakes on closed requests can only be set to
+    /// zero, freeing the points for other requests.
+    ///
+    /// # Errors
+    /// * `Error::SupportExceeded` - If the member's stakes would exceed `SUPPORT_POINTS`.
+    /// * `Error::ConvictionClosed` - If a closed request's stake is raised.
+    pub fn stake_support(
+        env: Env,
+        member: Address,
+        request_id: u64,
+        points: u32,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_member(&env, &member)?;
+
+        member.require_auth();
+
+        let mut request = Self::conviction_request(&env, request_id)?;
+        let stake_key = DataKey::ConvictionStake(request_id, member.clone());
+        let previous: u32 = env.storage().persistent().get(&stake_key).unwrap_or(0);
+        if request.status != ConvictionStatus::Open && points > previous {
+            return Err(Error::ConvictionClosed);
+        }
+
+        let staked_key = DataKey::StakedSupport(member.clone());
+        let staked: u32 = env.storage().persistent().get(&staked_key).unwrap_or(0);
+        let staked = staked - previous + points;
+        if staked > SUPPORT_POINTS {
+            return Err(Error::SupportExceeded);
+        }
+        env.storage().persistent().set(&staked_key, &staked);
+        env.storage().persistent().set(&stake_key, &points);
+
+        let requests_key = DataKey::StakedRequests(member.clone());
+        let mut requests: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&requests_key)
+            .unwrap_or(Vec::new(&env));
+        if previous == 0 && points > 0 {
+            requests.push_back(request_id);
+        } else if previous > 0 && points == 0 {
+            if let Some(index) = requests.first_index_of(request_id) {
+                requests.remove(index);
+            }
+        }
+        env.storage().persistent().set(&requests_key, &requests);
+
+        if request.status == ConvictionStatus::Open {
+            Self::accrue_conviction(&env, &mut request);
+        }
+        request.support = request.support - previous + points;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ConvictionRequest(request_id), &request);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("cv_stake")),
+            (request_id, member, points, request.conviction),
+        );
+
+        Ok(())
+    }
+
+    /// Bring a request's conviction up to date and, if it has reached the
+    /// threshold for the current treasury balance, pass it by queueing a
+    /// treasury withdrawal (see `execute_proposal` on the trust model).
+    /// Callable by anyone.
+    ///
+    /// # Returns
+    /// The treasury transaction ID.
+    ///
+    /// # Errors
+    /// * `Error::ConvictionBelowThreshold` - If conviction is still below the
+    ///   threshold, or the request asks for more than `max_ratio` of the balance.
+    pub fn check_conviction(env: Env, request_id: u64) -> Result<u64, Error> {
+        Self::require_initialized(&env)?;
+
+        let mut request = Self::conviction_request(&env, request_id)?;
+        if request.status != ConvictionStatus::Open {
+            return Err(Error::ConvictionClosed);
+        }
+        Self::accrue_conviction(&env, &mut request);
+
+        let threshold = Self::conviction_threshold(&env, request.amount)
+            .ok_or(Error::ConvictionBelowThreshold)?;
+        if request.conviction < threshold {
+            return Err(Error::ConvictionBelowThreshold);
+        }
+
+        let treasury_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .ok_or(Error::NotInitialized)?;
+        let expires_at = env
+            .ledger()
+            .timestamp()
+            .saturating_add(Self::conviction_params(&env).expires_after);
+        let tx_id = TreasuryContractClient::new(&env, &treasury_address)
+            .try_propose_withdrawal(
+                &env.current_contract_address(),
+                &request.recipient,
+                &request.amount,
+                &request.memo,
+                &expires_at,
+            )
+            .map_err(|_| Error::ProposalRejected)?
+            .map_err(|_| Error::ProposalRejected)?;
+
+        request.status = ConvictionStatus::Passed;
+        request.treasury_tx_id = Some(tx_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ConvictionRequest(request_id), &request);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("cv_pass")),
+            (request_id, request.conviction, tx_id),
+        );
+
+        Ok(tx_id)
+    }
+
+    /// Withdraw an open conviction request. Proposer only; stakers then
+    /// free their points with `stake_support(.., 0)`.
+    pub fn withdraw_conviction_request(
+        env: Env,
+        proposer: Address,
+        request_id: u64,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        proposer.require_auth();
+
+        let mut request = Self::conviction_request(&env, request_id)?;
+        if request.proposer != proposer {
+            return Err(Error::Unauthorized);
+        }
+        if request.status != ConvictionStatus::Open {
+            return Err(Error::ConvictionClosed);
+        }
+        request.status = ConvictionStatus::Withdrawn;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ConvictionRequest(request_id), &request);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("cv_wdraw")),
+            request_id,
+        );
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Member Management (Internal)
+    // ========================================================================
+
+    fn internal_add_member(env: &Env, member: &Address) -> Result<(), Error> {
+        let mut members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env));
+
+        // Check not already a member
+        for i in 0..members.len() {
+            if members.get(i).unwrap() == *member {
+                return Ok(()); // Already a member, no-op
+            }
+        }
+
+        members.push_back(member.clone());
+        env.storage().instance().set(&DataKey::Members, &members);
+        Ok(())
+    }
+
+    fn internal_remove_member(env: &Env, member: &Address) -> Result<(), Error> {
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env));
+
+        let mut new_members = Vec::new(env);
+        for i in 0..members.len() {
+            let m = members.get(i).unwrap();
+            if m != *member {
+                new_members.push_back(m);
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Members, &new_members);
+        Self::clear_stakes(env, member);
+        Ok(())
+    }
+
+    /// Withdraw a former member's support from every conviction request,
+    /// keeping the conviction open requests accrued while it was staked.
+    fn clear_stakes(env: &Env, member: &Address) {
+        let requests_key = DataKey::StakedRequests(member.clone());
+        let requests: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&requests_key)
+            .unwrap_or(Vec::new(env));
+        for request_id in requests.iter() {
+            let stake_key = DataKey::ConvictionStake(request_id, member.clone());
+            let points: u32 = env.storage().persistent().get(&stake_key).unwrap_or(0);
+            env.storage().persistent().remove(&stake_key);
+            let mut request = match Self::conviction_request(env, request_id) {
+                Ok(request) => request,
+                Err(_) => continue,
+            };
+            if request.status == ConvictionStatus::Open {
+                Self::accrue_conviction(env, &mut request);
+            }
+            request.support -= points;
+            env.storage()
+                .persistent()
+                .set(&DataKey::ConvictionRequest(request_id), &request);
+
+            env.events().publish(
+                (symbol_short!("gov"), symbol_short!("cv_stake")),
+                (request_id, member.clone(), 0u32, request.conviction),
+            );
+        }
+        env.storage().persistent().remove(&requests_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::StakedSupport(member.clone()));
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get a proposal by its ID.
+    /// A proposal past its review period reads as Active.
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Result<Proposal, Error> {
+        let mut proposal = Self::load_proposal(&env, proposal_id).ok_or(Error::ProposalNotFound)?;
+        Self::open_voting(&env, &mut proposal);
+        Ok(proposal)
+    }
+
+    /// Get a multi-choice proposal's options, tallies and winner, if any.
+    pub fn get_choice(env: Env, proposal_id: u64) -> Option<Choice> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Choice(proposal_id))
+    }
+
+    /// Get the ed25519 key `member` signs relayed votes with, if registered.
+    pub fn get_vote_key(env: Env, member: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::VoteKey(member))
+    }
+
+    /// The nonce `voter`'s next signed vote must carry.
+    pub fn get_vote_nonce(env: Env, voter: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VoteNonce(voter))
+            .unwrap_or(0)
+    }
+
+    /// Get an optimistic proposal's veto percentage, or `None` for other
+    /// proposals.
+    pub fn get_veto_percent(env: Env, proposal_id: u64) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Optimistic(proposal_id))
+    }
+
+    /// Get the explicit treasury parameters of a Funding proposal, if any.
+    pub fn get_funding_request(env: Env, proposal_id: u64) -> Option<FundingRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FundingRequest(proposal_id))
+    }
+
+    /// Get governance configuration.
+    pub fn get_config(env: Env) -> Result<GovConfig, Error> {
+        Self::require_initialized(&env)?;
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(&env));
+        let quorum_percent: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::QuorumPercent)
+            .unwrap_or(50);
+        let voting_period: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VotingPeriod)
+            .unwrap_or(1000);
+        let proposal_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0);
+        let execution_delay: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ExecutionDelay)
+            .unwrap_or(0);
+        let proposal_bond: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalBond)
+            .unwrap_or(0);
+
+        Ok(GovConfig {
+            admin,
+            member_count: members.len(),
+            quorum_percent,
+            voting_period,
+            proposal_count,
+            execution_delay,
+            proposal_bond,
+            voting_clock: Self::voting_clock(&env),
+            review_delay: env
+                .storage()
+                .instance()
+                .get(&DataKey::ReviewDelay)
+                .unwrap_or(0),
+            voting_mode: env
+                .storage()
+                .instance()
+                .get(&DataKey::VotingMode)
+                .unwrap_or(VotingMode::OneMemberOneVote),
+            veto_percent: Self::veto_percent(&env),
+        })
+    }
+
+    /// Get the list of DAO members.
+    pub fn get_members(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a conviction request with its conviction brought up to date.
+    pub fn get_conviction_request(env: Env, request_id: u64) -> Result<ConvictionRequest, Error> {
+        let mut request = Self::conviction_request(&env, request_id)?;
+        if request.status == ConvictionStatus::Open {
+            Self::accrue_conviction(&env, &mut request);
+        }
+        Ok(request)
+    }
+
+    /// The conviction a request needs to pass at the current treasury
+    /// balance; `None` if it asks for `max_ratio` of the balance or more.
+    pub fn get_conviction_threshold(env: Env, request_id: u64) -> Result<Option<u128>, Error> {
+        let request = Self::conviction_request(&env, request_id)?;
+        Ok(Self::conviction_threshold(&env, request.amount))
+    }
+
+    /// Get the conviction voting parameters.
+    pub fn get_conviction_params(env: Env) -> ConvictionParams {
+        Self::conviction_params(&env)
+    }
+
+    /// Get the voice credit policy.
+    pub fn get_credit_policy(env: Env) -> CreditPolicy {
+        Self::credit_policy(&env)
+    }
+
+    /// Voice credits `member` has left to spend this period.
+    pub fn get_voice_credits(env: Env, member: Address) -> u64 {
+        let (usage, allotment) = Self::credit_usage(&env, &member);
+        allotment.saturating_sub(usage.spent)
+    }
+
+    /// Get the voting rules new proposals of `action` would be created with.
+    pub fn get_action_rules(env: Env, action: ProposalAction) -> ActionRules {
+        Self::action_rules(&env, &action)
+    }
+
+    /// Get participation totals over finalized proposals, excluding canceled ones.
+    pub fn get_quorum_stats(env: Env) -> QuorumStats {
+        Self::quorum_stats(&env)
+    }
+
+    /// Check if an address has voted on a proposal.
+    pub fn has_voted(env: Env, proposal_id: u64, voter: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Vote(proposal_id, voter))
+    }
+
+    /// Get how `voter` voted on a proposal: choice, weight, reason and time.
+    pub fn get_vote(env: Env, proposal_id: u64, voter: Address) -> Option<VoteRecord> {
+        let value: Val = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vote(proposal_id, voter))?;
+        if let Ok(record) = VoteRecord::try_from_val(&env, &value) {
+            return Some(record);
+        }
+        // Votes cast before vote records stored only the choice
+        let vote_for = bool::try_from_val(&env, &value).ok()?;
+        Some(VoteRecord {
+            vote_for,
+            choices: Vec::new(&env),
+            weight: 1,
+            reason: VoteReason::None,
+            timestamp: 0,
+        })
+    }
+
+    /// List proposals page by page, optionally filtered by status.
+    ///
+    /// Without a filter, proposals are returned in ID order. With a filter,
+    /// only that status's index is read, in no particular order; `Pending`
+    /// and `Active` share an index and are told apart by their current
+    /// status. Each returned `status` is current.
+    ///
+    /// # Arguments
+    /// * `status_filter` - Only return proposals in this status.
+    /// * `start` - Zero-based offset into the (filtered) result set.
+    /// * `limit` - Maximum entries to return, capped at `MAX_PAGE_SIZE`.
+    pub fn list_proposals(
+        env: Env,
+        status_filter: Option<ProposalStatus>,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Proposal> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut page = Vec::new(&env);
+        if limit == 0 {
+            return page;
+        }
+
+        let status = match status_filter {
+            None => {
+                let proposal_count: u64 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::ProposalCounter)
+                    .unwrap_or(0);
+                let last = proposal_count.min(start as u64 + limit as u64);
+                for id in (start as u64 + 1)..=last {
+                    if let Ok(proposal) = Self::get_proposal(env.clone(), id) {
+                        page.push_back(proposal);
+                    }
+                }
+                return page;
+            }
+            Some(status) => status,
+        };
+
+        let index = Self::index_for(&status);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusCount(index.clone()))
+            .unwrap_or(0);
+        let shared = index != status || status == ProposalStatus::Active;
+        let mut position = if shared { 0 } else { start };
+        let mut skipped = 0_u32;
+        while position < count && page.len() < limit {
+            let id: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::StatusEntry(index.clone(), position))
+                .unwrap_or(0);
+            position += 1;
+            let proposal = match Self::get_proposal(env.clone(), id) {
+                Ok(proposal) if proposal.status == status => proposal,
+                _ => continue,
+            };
+            if shared && skipped < start {
+                skipped += 1;
+                continue;
+            }
+            page.push_back(proposal);
+        }
+        page
+    }
+
+    /// List a proposal's votes in voting order.
+    ///
+    /// # Arguments
+    /// * `start` - Zero-based offset into the proposal's votes.
+    /// * `limit` - Maximum entries to return, capped at `MAX_PAGE_SIZE`.
+    pub fn list_votes(env: Env, proposal_id: u64, start: u32, limit: u32) -> Vec<VoteEntry> {
+        let mut page = Vec::new(&env);
+        let total_votes = match Self::get_proposal(env.clone(), proposal_id) {
+            Ok(proposal) => proposal.total_votes,
+            Err(_) => return page,
+        };
+        let end = total_votes.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        for position in start..end {
+            let voter: Address = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::VoteAt(proposal_id, position))
+            {
+                Some(voter) => voter,
+                None => continue,
+            };
+            if let Some(record) = Self::get_vote(env.clone(), proposal_id, voter.clone()) {
+                page.push_back(VoteEntry {
+                    proposal_id,
+                    voter,
+                    record,
+                });
+            }
+        }
+        page
+    }
+
+    /// List the votes `voter` has cast, in voting order.
+    ///
+    /// # Arguments
+    /// * `start` - Zero-based offset into the voter's votes.
+    /// * `limit` - Maximum entries to return, capped at `MAX_PAGE_SIZE`.
+    pub fn get_votes_by_voter(env: Env, voter: Address, start: u32, limit: u32) -> Vec<VoteEntry> {
+        let mut page = Vec::new(&env);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VoterVoteCount(voter.clone()))
+            .unwrap_or(0);
+        let end = count.min(start.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        for position in start..end {
+            let proposal_id: u64 = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::VoterVoteAt(voter.clone(), position))
+            {
+                Some(proposal_id) => proposal_id,
+                None => continue,
+            };
+            if let Some(record) = Self::get_vote(env.clone(), proposal_id, voter.clone()) {
+                page.push_back(VoteEntry {
+                    proposal_id,
+                    voter: voter.clone(),
+                    record,
+                });
+            }
+        }
+        page
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Transfer admin role to a new address.
+    pub fn transfer_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &current_admin)?;
+
+        current_admin.require_auth();
+
+        Self::require_acl_admin_or_above(&env, &new_admin)?;
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("admin")),
+            (current_admin, new_admin.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Update the quorum percentage. Admin only.
+    pub fn set_quorum(env: Env, admin: Address, new_quorum: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::QuorumPercent, &new_quorum);
+
+        env.events()
+            .publish((symbol_short!("gov"), symbol_short!("quorum")), new_quorum);
+
+        Ok(())
+    }
+
+    /// Switch the clock new proposals' voting windows are measured in, with
+    /// the default `voting_period` in the new unit (ledgers or seconds).
+    /// Per-action rules are read in the new unit too and should be updated
+    /// alongside. Proposals already created keep their clock, so ledger-based
+    /// proposals remain finalizable after switching. Admin only.
+    pub fn set_voting_clock(
+        env: Env,
+        admin: Address,
+        clock: VotingClock,
+        voting_period: u32,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if voting_period == 0 {
+            return Err(Error::InvalidRules);
+        }
+        env.storage().instance().set(&DataKey::VotingClock, &clock);
+        env.storage()
+            .instance()
+            .set(&DataKey::VotingPeriod, &voting_period);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("clock")),
+            (clock, voting_period),
+        );
+
+        Ok(())
+    }
+
+    /// Set how votes on new proposals are weighted. Admin only.
+    pub fn set_voting_mode(env: Env, admin: Address, mode: VotingMode) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::VotingMode, &mode);
+
+        env.events()
+            .publish((symbol_short!("gov"), symbol_short!("mode")), mode);
+
+        Ok(())
+    }
+
+    /// Set the default voice credit allotment and period. Admin only.
+    pub fn set_credit_policy(env: Env, admin: Address, policy: CreditPolicy) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if policy.period == 0 {
+            return Err(Error::InvalidCredits);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::CreditPolicy, &policy);
+
+        env.events()
+            .publish((symbol_short!("gov"), symbol_short!("credits")), policy);
+
+        Ok(())
+    }
+
+    /// Override one member's per-period voice credit allotment, or clear the
+    /// override with `None`. Admin only.
+    pub fn set_member_credits(
+        env: Env,
+        admin: Address,
+        member: Address,
+        allotment: Option<u64>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        let key = DataKey::MemberCredits(member.clone());
+        match allotment {
+            Some(allotment) => env.storage().persistent().set(&key, &allotment),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("mcredits")),
+            (member, allotment),
+        );
+
+        Ok(())
+    }
+
+    /// Set the percentage of members whose vetoes block new optimistic
+    /// proposals (0-100). Admin only.
+    pub fn set_veto_percent(env: Env, admin: Address, percent: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if percent > 100 {
+            return Err(Error::InvalidRules);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::VetoPercent, &percent);
+
+        env.events()
+            .publish((symbol_short!("gov"), symbol_short!("veto_pct")), percent);
+
+        Ok(())
+    }
+
+    /// Set conviction voting's decay, max ratio, weight and withdrawal
+    /// expiry. Admin only.
+    pub fn set_conviction_params(
+        env: Env,
+        admin: Address,
+        params: ConvictionParams,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        if params.decay as u128 >= PPM
+            || params.max_ratio == 0
+            || params.max_ratio as u128 > PPM
+            || params.weight == 0
+            || params.expires_after == 0
+        {
+            return Err(Error::InvalidConviction);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ConvictionParams, &params);
+
+        env.events()
+            .publish((symbol_short!("gov"), symbol_short!("cv_param")), params);
+
+        Ok(())
+    }
+
+    /// Set the review period new proposals wait in `Pending` before voting
+    /// opens, in units of the voting clock. Voting periods start after it.
+    /// Admin only.
+    pub fn set_review_delay(env: Env, admin: Address, delay: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::ReviewDelay, &delay);
+
+        env.events()
+            .publish((symbol_short!("gov"), symbol_short!("review")), delay);
+
+        Ok(())
+    }
+
+    /// Set the voting rules for new proposals of `action`. Proposals already
+    /// created keep the rules they were created with. Admin only.
+    pub fn set_action_rules(
+        env: Env,
+        admin: Address,
+        action: ProposalAction,
+        rules: ActionRules,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        Self::validate_rules(&rules)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::ActionRules(action.clone()), &rules);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("rules")),
+            (action, rules),
+        );
+
+        Ok(())
+    }
+
+    /// Set how long a passed proposal waits before it can be executed, on the
+    /// proposal's clock: ledgers under the Ledger clock, seconds under the
+    /// Timestamp clock. Update it alongside `set_voting_clock`. Members can
+    /// ragequit during this window. Admin only.
+    pub fn set_execution_delay(env: Env, admin: Address, delay: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ExecutionDelay, &delay);
+
+        env.events()
+            .publish((symbol_short!("gov"), symbol_short!("delay")), delay);
+
+        Ok(())
+    }
+
+    /// Upgrade contract WASM. Admin only.
+    pub fn upgrade(
+        env: Env,
+        admin: Address,
+        new_wasm_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        admin.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Internal Helpers
+    // ========================================================================
+
+    fn require_initialized(env: &Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::NotInitialized);
+        }
+        Ok(())
+    }
+
+    /// Start a passed proposal's timelock on its own clock: the execution
+    /// delay counts ledgers under the Ledger clock and seconds under the
+    /// Timestamp clock.
+    fn start_timelock(env: &Env, proposal: &mut Proposal) {
+        let delay: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ExecutionDelay)
+            .unwrap_or(0);
+        match proposal.clock {
+            VotingClock::Ledger => {
+                proposal.executable_at = env.ledger().sequence().saturating_add(delay);
+            }
+            VotingClock::Timestamp => {
+                proposal.executable_time = env.ledger().timestamp().saturating_add(delay as u64);
+            }
+        }
+    }
+
+    fn timelock_elapsed(env: &Env, proposal: &Proposal) -> bool {
+        match proposal.clock {
+            VotingClock::Ledger => env.ledger().sequence() >= proposal.executable_at,
+            VotingClock::Timestamp => env.ledger().timestamp() >= proposal.executable_time,
+        }
+    }
+
+    fn voting_clock(env: &Env) -> VotingClock {
+        env.storage()
+            .instance()
+            .get(&DataKey::VotingClock)
+            .unwrap_or(VotingClock::Ledger)
+    }
+
+    fn clock_now(env: &Env, clock: VotingClock) -> u64 {
+        match clock {
+            VotingClock::Ledger => env.ledger().sequence() as u64,
+            VotingClock::Timestamp => env.ledger().timestamp(),
+        }
+    }
+
+    /// Move a Pending proposal to Active once its review period is over.
+    fn open_voting(env: &Env, proposal: &mut Proposal) {
+        if proposal.status == ProposalStatus::Pending
+            && Self::clock_now(env, proposal.clock) >= proposal.voting_starts_at
+        {
+            proposal.status = ProposalStatus::Active;
+        }
+    }
+
+    /// The status index a proposal in `status` is kept in.
+    fn index_for(status: &ProposalStatus) -> ProposalStatus {
+        match status {
+            ProposalStatus::Pending => ProposalStatus::Active,
+            other => other.clone(),
+        }
+    }
+
+    /// Move a proposal to `status`, keeping the status indexes and its
+    /// voters' open For votes in step.
+    fn set_status(env: &Env, proposal: &mut Proposal, status: ProposalStatus) {
+        if Self::index_for(&proposal.status) != Self::index_for(&status) {
+            Self::unindex_status(env, proposal.id, &proposal.status);
+            Self::index_status(env, proposal.id, &status);
+        }
+        if Self::blocks_ragequit(&proposal.action, &proposal.status)
+            && !Self::blocks_ragequit(&proposal.action, &status)
+        {
+            Self::settle_yes_votes(env, proposal.id);
+        }
+        proposal.status = status;
+    }
+
+    /// Whether a For vote on a proposal in `status` blocks ragequit. Votes
+    /// block until the outcome is known; a passed Funding proposal keeps
+    /// blocking until its treasury transfer is made.
+    fn blocks_ragequit(action: &ProposalAction, status: &ProposalStatus) -> bool {
+        match status {
+            ProposalStatus::Pending | ProposalStatus::Active => true,
+            ProposalStatus::Passed | ProposalStatus::FundingFailed => {
+                *action == ProposalAction::Funding
+            }
+            _ => false,
+        }
+    }
+
+    /// Record a For vote on the proposal and count it against the voter's
+    /// ragequit.
+    fn open_yes_vote(env: &Env, proposal_id: u64, voter: &Address) {
+        let count_key = DataKey::YesVoterCount(proposal_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::YesVoterAt(proposal_id, count), voter);
+        env.storage().persistent().set(&count_key, &(count + 1));
+
+        let open_key = DataKey::OpenYesVotes(voter.clone());
+        let open: u32 = env.storage().persistent().get(&open_key).unwrap_or(0);
+        env.storage().persistent().set(&open_key, &(open + 1));
+    }
+
+    /// Release the For votes on a proposal that no longer blocks ragequit.
+    fn settle_yes_votes(env: &Env, proposal_id: u64) {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::YesVoterCount(proposal_id))
+            .unwrap_or(0);
+        for position in 0..count {
+            let voter: Option<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::YesVoterAt(proposal_id, position));
+            if let Some(voter) = voter {
+                let open_key = DataKey::OpenYesVotes(voter);
+                let open: u32 = env.storage().persistent().get(&open_key).unwrap_or(0);
+                env.storage()
+                    .persistent()
+                    .set(&open_key, &open.saturating_sub(1));
+            }
+        }
+    }
+
+    fn index_status(env: &Env, proposal_id: u64, status: &ProposalStatus) {
+        let index = Self::index_for(status);
+        let count_key = DataKey::StatusCount(index.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StatusEntry(index, count), &proposal_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StatusSlot(proposal_id), &count);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    /// Remove a proposal from its status index by moving the last entry
+    /// into its slot. Proposals created before the indexes are skipped.
+    fn unindex_status(env: &Env, proposal_id: u64, status: &ProposalStatus) {
+        let slot: u32 = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::StatusSlot(proposal_id))
+        {
+            Some(slot) => slot,
+            None => return,
+        };
+        let index = Self::index_for(status);
+        let count_key = DataKey::StatusCount(index.clone());
+        let last = env
+            .storage()
+            .persistent()
+            .get::<_, u32>(&count_key)
+            .unwrap_or(0)
+            .saturating_sub(1);
+        if slot != last {
+            let moved: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::StatusEntry(index.clone(), last))
+                .unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&DataKey::StatusEntry(index.clone(), slot), &moved);
+            env.storage()
+                .persistent()
+                .set(&DataKey::StatusSlot(moved), &slot);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::StatusEntry(index, last));
+        env.storage().persistent().set(&count_key, &last);
+    }
+
+    /// Append a vote to the proposal's and the voter's vote indexes.
+    fn index_vote(env: &Env, proposal_id: u64, position: u32, voter: &Address) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::VoteAt(proposal_id, position), voter);
+        let count_key = DataKey::VoterVoteCount(voter.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::VoterVoteAt(voter.clone(), count), &proposal_id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    fn veto_percent(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::VetoPercent)
+            .unwrap_or(10)
+    }
+
+    /// Whether a proposal takes ballots other than yes/no votes.
+    fn has_special_ballot(env: &Env, proposal_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Choice(proposal_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Optimistic(proposal_id))
+    }
+
+    fn conviction_params(env: &Env) -> ConvictionParams {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConvictionParams)
+            .unwrap_or(ConvictionParams {
+                decay: 999_000,
+                max_ratio: 200_000,
+                weight: 2_000,
+                expires_after: 7 * 24 * 3600,
+            })
+    }
+
+    fn conviction_request(env: &Env, request_id: u64) -> Result<ConvictionRequest, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ConvictionRequest(request_id))
+            .ok_or(Error::ConvictionNotFound)
+    }
+
+    /// Decay conviction over the ledgers since the last update while adding
+    /// the current support each ledger:
+    /// `y' = a^n * y + support * (1 - a^n) / (1 - a)`.
+    fn accrue_conviction(env: &Env, request: &mut ConvictionRequest) {
+        let now = env.ledger().sequence();
+        let elapsed = now.saturating_sub(request.updated_at);
+        if elapsed > 0 {
+            let decay = Self::conviction_params(env).decay as u128;
+            let retained = ppm_pow(decay, elapsed);
+            request.conviction = request.conviction * retained / PPM
+                + request.support as u128 * (PPM - retained) / (PPM - decay);
+        }
+        request.updated_at = now;
+    }
+
+    /// `weight * total_support / ((1 - a) * (max_ratio - share)^2)`, where
+    /// `share` is the requested fraction of the treasury balance and total
+    /// support is every member's `SUPPORT_POINTS`. `None` if the share is at
+    /// or above `max_ratio`.
+    fn conviction_threshold(env: &Env, amount: i128) -> Option<u128> {
+        let treasury_address: Address = env.storage().instance().get(&DataKey::TreasuryAddress)?;
+        let balance = TreasuryContractClient::new(env, &treasury_address)
+            .try_get_balance()
+            .ok()?
+            .ok()?;
+        if balance <= 0 {
+            return None;
+        }
+        let params = Self::conviction_params(env);
+        let share = (amount as u128).saturating_mul(PPM) / balance as u128;
+        let headroom = (params.max_ratio as u128)
+            .checked_sub(share)
+            .filter(|h| *h > 0)?;
+
+        let total_support = Self::get_members(env.clone()).len() as u128 * SUPPORT_POINTS as u128;
+        Some(
+            params.weight as u128 * total_support * PPM * PPM
+                / ((PPM - params.decay as u128) * headroom * headroom),
+        )
+    }
+
+    fn credit_policy(env: &Env) -> CreditPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::CreditPolicy)
+            .unwrap_or(CreditPolicy {
+                allotment: 100,
+                period: 120_960,
+            })
+    }
+
+    /// A member's credit usage in the current period and their allotment.
+    fn credit_usage(env: &Env, member: &Address) -> (CreditUsage, u64) {
+        let policy = Self::credit_policy(env);
+        let period = env.ledger().sequence() as u64 / policy.period as u64;
+        let allotment: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MemberCredits(member.clone()))
+            .unwrap_or(policy.allotment);
+        let usage: CreditUsage = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CreditUsage(member.clone()))
+            .filter(|usage: &CreditUsage| usage.period == period)
+            .unwrap_or(CreditUsage { period, spent: 0 });
+        (usage, allotment)
+    }
+
+    /// The winning option of a multi-choice proposal, if any.
+    fn choice_winner(env: &Env, proposal: &Proposal, choice: &Choice) -> Option<u32> {
+        if choice.method != TallyMethod::InstantRunoff {
+            return Self::sole_leader(&choice.tallies);
+        }
+
+        // Load each ballot once; every elimination round recounts them
+        let mut ballots: Vec<Vec<u32>> = Vec::new(env);
+        for position in 0..proposal.total_votes {
+            if let Some(ballot) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::BallotAt(proposal.id, position))
+            {
+                ballots.push_back(ballot);
+            }
+        }
+        let option_count = choice.tallies.len();
+        let mut eliminated: u32 = 0;
+        loop {
+            // Count each ballot toward its highest-ranked remaining option
+            let mut counts = [0u32; MAX_CHOICE_OPTIONS as usize];
+            let mut continuing = 0u32;
+            for ballot in ballots.iter() {
+                if let Some(option) = ballot.iter().find(|o| eliminated & (1 << o) == 0) {
+                    counts[option as usize] += 1;
+                    continuing += 1;
+                }
+            }
+            if continuing == 0 {
+                return None;
+            }
+
+            let remaining = (0..option_count).filter(|o| eliminated & (1 << o) == 0);
+            let leader = remaining
+                .clone()
+                .max_by_key(|o| (counts[*o as usize], core::cmp::Reverse(*o)))?;
+            if counts[leader as usize] * 2 > continuing {
+                return Some(leader);
+            }
+            let last = remaining.min_by_key(|o| (counts[*o as usize], core::cmp::Reverse(*o)))?;
+            if counts[last as usize] == counts[leader as usize] {
+                // Every remaining option is tied
+                return None;
+            }
+            eliminated |= 1 << last;
+        }
+    }
+
+    /// The option with strictly the most votes, if it has any.
+    fn sole_leader(tallies: &Vec<u32>) -> Option<u32> {
+        let mut leader: Option<u32> = None;
+        let mut best = 0u32;
+        let mut tied = false;
+        for (option, tally) in tallies.iter().enumerate() {
+            if tally > best {
+                leader = Some(option as u32);
+                best = tally;
+                tied = false;
+            } else if tally == best && best > 0 {
+                tied = true;
+            }
+        }
+        if tied {
+            None
+        } else {
+            leader
+        }
+    }
+
+    /// Whether the proposal's voting window has closed on its own clock.
+    fn voting_ended(env: &Env, proposal: &Proposal) -> bool {
+        match proposal.clock {
+            VotingClock::Ledger => env.ledger().sequence() > proposal.ends_at,
+            VotingClock::Timestamp => env.ledger().timestamp() > proposal.ends_time,
+        }
+    }
+
+    /// Votes needed for `quorum_percent` with the current member count.
+    fn quorum_threshold(env: &Env, quorum_percent: u32) -> u32 {
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env));
+        (members.len() * quorum_percent) / 100
+    }
+
+    /// Rules for new proposals of `action`: the per-action override, or the
+    /// global quorum and voting period with a simple majority.
+    /// Load a proposal. Proposals stored before `ProposalExt` existed were
+    /// created on the ledger clock under the global voting rules, with no
+    /// bond, review period or timelock.
+    fn load_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
+        let stored: StoredProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))?;
+        let ext: ProposalExt = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposalExt(proposal_id))
+            .unwrap_or_else(|| ProposalExt {
+                executable_at: 0,
+                treasury_tx_id: None,
+                bond: 0,
+                rules: Self::global_rules(env),
+                clock: VotingClock::Ledger,
+                created_time: 0,
+                ends_time: 0,
+                voting_starts_at: stored.created_at as u64,
+                mode: VotingMode::OneMemberOneVote,
+                executable_time: 0,
+            });
+        Some(Proposal {
+            id: stored.id,
+            title: stored.title,
+            description: stored.description,
+            action: stored.action,
+            proposer: stored.proposer,
+            votes_for: stored.votes_for,
+            votes_against: stored.votes_against,
+            total_votes: stored.total_votes,
+            status: stored.status,
+            created_at: stored.created_at,
+            ends_at: stored.ends_at,
+            amount: stored.amount,
+            target: stored.target,
+            executable_at: ext.executable_at,
+            treasury_tx_id: ext.treasury_tx_id,
+            bond: ext.bond,
+            rules: ext.rules,
+            clock: ext.clock,
+            created_time: ext.created_time,
+            ends_time: ext.ends_time,
+            voting_starts_at: ext.voting_starts_at,
+            mode: ext.mode,
+            executable_time: ext.executable_time,
+        })
+    }
+
+    fn save_proposal(env: &Env, proposal: &Proposal) {
+        let stored = StoredProposal {
+            id: proposal.id,
+            title: proposal.title.clone(),
+            description: proposal.description.clone(),
+            action: proposal.action.clone(),
+            proposer: proposal.proposer.clone(),
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            total_votes: proposal.total_votes,
+            status: proposal.status.clone(),
+            created_at: proposal.created_at,
+            ends_at: proposal.ends_at,
+            amount: proposal.amount,
+            target: proposal.target.clone(),
+        };
+        let ext = ProposalExt {
+            executable_at: proposal.executable_at,
+            treasury_tx_id: proposal.treasury_tx_id,
+            bond: proposal.bond,
+            rules: proposal.rules.clone(),
+            clock: proposal.clock,
+            created_time: proposal.created_time,
+            ends_time: proposal.ends_time,
+            voting_starts_at: proposal.voting_starts_at,
+            mode: proposal.mode,
+            executable_time: proposal.executable_time,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal.id), &stored);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProposalExt(proposal.id), &ext);
+    }
+
+    fn action_rules(env: &Env, action: &ProposalAction) -> ActionRules {
+        env.storage()
+            .instance()
+            .get(&DataKey::ActionRules(action.clone()))
+            .unwrap_or_else(|| Self::global_rules(env))
+    }
+
+    /// Voting rules of actions without their own.
+    fn global_rules(env: &Env) -> ActionRules {
+        ActionRules {
+            quorum_percent: env
+                .storage()
+                .instance()
+                .get(&DataKey::QuorumPercent)
+                .unwrap_or(50),
+            approval_percent: 50,
+            voting_period: env
+                .storage()
+                .instance()
+                .get(&DataKey::VotingPeriod)
+                .unwrap_or(1000),
+        }
+    }
+
+    fn validate_rules(rules: &ActionRules) -> Result<(), Error> {
+        if rules.quorum_percent > 100 || rules.approval_percent > 100 || rules.voting_period == 0 {
+            return Err(Error::InvalidRules);
+        }
+        Ok(())
+    }
+
+    fn quorum_stats(env: &Env) -> QuorumStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::QuorumStats)
+            .unwrap_or(QuorumStats {
+                finalized: 0,
+                quorum_reached: 0,
+                votes_cast: 0,
+                eligible_votes: 0,
+                canceled: 0,
+            })
+    }
+
+    fn treasury_asset(env: &Env) -> Result<Address, Error> {
+        let treasury_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .ok_or(Error::NotInitialized)?;
+        let config = TreasuryContractClient::new(env, &treasury_address)
+            .try_get_config()
+            .map_err(|_| Error::BondFailed)?
+            .map_err(|_| Error::BondFailed)?;
+        Ok(config.asset)
+    }
+
+    /// Return a settled proposal's bond to the proposer, or donate it to the
+    /// treasury if it is slashed.
+    ///
+    /// The treasury pulls a donation with a nested `transfer`, so this
+    /// contract pre-authorizes that sub-invocation.
+    fn settle_bond(env: &Env, proposal: &Proposal, returned: bool) -> Result<(), Error> {
+        if proposal.bond <= 0 {
+            return Ok(());
+        }
+        let asset = Self::treasury_asset(env)?;
+        let this = env.current_contract_address();
+        if returned {
+            token::Client::new(env, &asset).transfer(&this, &proposal.proposer, &proposal.bond);
+        } else {
+            let treasury: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::TreasuryAddress)
+                .ok_or(Error::NotInitialized)?;
+            env.authorize_as_current_contract(vec![
+                env,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: asset.clone(),
+                        fn_name: Symbol::new(env, "transfer"),
+                        args: (this.clone(), treasury.clone(), proposal.bond).into_val(env),
+                    },
+                    sub_invocations: vec![env],
+                }),
+            ]);
+            TreasuryContractClient::new(env, &treasury)
+                .try_donate(&this, &proposal.bond)
+                .map_err(|_| Error::BondFailed)?
+                .map_err(|_| Error::BondFailed)?;
+        }
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("bond")),
+            (proposal.id, returned, proposal.bond),
+        );
+        Ok(())
+    }
+
+    fn get_acl(env: &Env) -> Result<AccessControlContractClient, Error> {
+        let acl_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AclAddress)
+            .ok_or(Error::NotInitialized)?;
+        Ok(AccessControlContractClient::new(env, &acl_address))
+    }
+
+    fn require_acl_admin_or_above(env: &Env, caller: &Address) -> Result<(), Error> {
+        let acl = Self::get_acl(env)?;
+        if !acl.is_admin_or_above(caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn require_acl_member_or_above(env: &Env, caller: &Address) -> Result<(), Error> {
+        let acl = Self::get_acl(env)?;
+        if !acl.is_member_or_above(caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn require_member(env: &Env, caller: &Address) -> Result<(), Error> {
+        let members: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env));
+
+        let mut is_member = false;
+        for i in 0..members.len() {
+            if members.get(i).unwrap() == *caller {
+                is_member = true;
+                break;
+            }
+        }
+        if !is_member {
+            return Err(Error::NotAMember);
+        }
+        Self::require_acl_member_or_above(env, caller)
+    }
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if *caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        Self::require_acl_admin_or_above(env, caller)
+    }
+}
+
+/// Integer square root, rounded down.
+fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from ceil(n / 2), which cannot overflow
+    let mut x = n;
+    let mut y = n / 2 + n % 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// `base^exp` for a parts-per-million `base` below 1, by squaring.
+fn ppm_pow(base: u128, mut exp: u32) -> u128 {
+    let mut result = PPM;
+    let mut base = base;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base / PPM;
+        }
+        base = base * base / PPM;
+        exp >>= 1;
+    }
+    result
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use soroban_sdk::Env;
+    use stellar_sentinel_access_control::{
+        AccessControlContract, AccessControlContractClient, Role,
+    };
+    use stellar_sentinel_treasury::{MilestoneStatus, MilestoneTerms, TreasuryContract};
+
+    fn deploy_acl(env: &Env, owner: &Address) -> Address {
+        let acl_id = env.register_contract(None, AccessControlContract);
+        let acl_client = AccessControlContractClient::new(env, &acl_id);
+        acl_client.initialize(owner);
+        acl_id
+    }
+
+    fn assign_role(env: &Env, acl_id: &Address, admin: &Address, target: &Address, role: &Role) {
+        let acl_client = AccessControlContractClient::new(env, acl_id);
+        acl_client.assign_role(admin, target, role);
+    }
+
+    /// Register `governance` with a threshold-1 treasury through a
+    /// signer-approved transaction.
+    fn register_governance(
+        treasury: &TreasuryContractClient,
+        signer: &Address,
+        governance: &Address,
+    ) {
+        let expires_at = treasury.env.ledger().timestamp() + 1_000;
+        let tx_id =
+            treasury.propose_governance(signer, governance, &symbol_short!("gov"), &expires_at);
+        treasury.execute(signer, &tx_id);
+    }
+
+    fn setup_contract() -> (
+        Env,
+        Address,
+        Address,
+        Address,
+        GovernanceContractClient<'static>,
+    ) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, GovernanceContract);
+        let client = GovernanceContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let acl_id = deploy_acl(&env, &admin);
+        let treasury_id = Address::generate(&env);
+        (env, admin, acl_id, treasury_id, client)
+    }
+
+    #[test]
+    fn test_initialize() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+
+        client.initialize(&admin, &members, &50, &1000, &acl_id, &treasury_id);
+
+        let config = client.get_config();
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.member_count, 2);
+        assert_eq!(config.quorum_percent, 50);
+        assert_eq!(config.voting_period, 1000);
+    }
+
+    #[test]
+    fn test_create_proposal_and_vote() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+
+        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+        assign_role(&env, &acl_id, &admin, &member2, &Role::Member);
+        assign_role(&env, &acl_id, &admin, &member3, &Role::Member);
+
+        client.initialize(&admin, &members, &50, &1000, &acl_id, &treasury_id);
+
+        // Create a funding proposal
+        let proposal_id = client.create_proposal(
+            &member1,
+            &symbol_short!("fund_dev"),
+            &symbol_short!("dev_work"),
+            &ProposalAction::Funding,
+            &500_000,
+            &member1,
+        );
+        assert_eq!(proposal_id, 1);
+
+        // Vote
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member2, &proposal_id, &true);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.votes_for, 2);
+        assert_eq!(proposal.total_votes, 2);
+        assert_eq!(proposal.status, ProposalStatus::Active);
+    }
+
+    #[test]
+    fn test_has_voted() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+
+        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+
+        client.initialize(&admin, &members, &50, &1000, &acl_id, &treasury_id);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &symbol_short!("test"),
+            &symbol_short!("test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+        );
+
+        assert!(!client.has_voted(&proposal_id, &member1));
+
+        client.vote(&member1, &proposal_id, &true);
+
+        assert!(client.has_voted(&proposal_id, &member1));
+    }
+
+    #[test]
+    fn test_execution_timelock_delays_passed_proposals() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        client.set_execution_delay(&admin, &100);
+        assert_eq!(client.get_config().execution_delay, 100);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &symbol_short!("test"),
+            &symbol_short!("test"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+        );
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &proposal_id);
+
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.executable_at, env.ledger().sequence() + 100);
+        assert_eq!(
+            client.try_execute_proposal(&member1, &proposal_id),
+            Err(Ok(Error::TimelockActive))
+        );
+
+        env.ledger().with_mut(|l| l.sequence_number += 100);
+        client.execute_proposal(&member1, &proposal_id);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Executed
+        );
+    }
+
+    #[test]
+    fn test_ragequit_during_funding_timelock() {
+        let (env, admin, acl_id, _, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+
+        // Real treasury with share minting, funded by member1 and member2.
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &1,
+            &Vec::from_array(&env, [client.address.clone()]),
+            &acl_id,
+        );
+        treasury.set_share_minting(&admin, &true);
+        register_governance(&treasury, &client.address, &client.address);
+        sac.mint(&member1, &1_000);
+        sac.mint(&member2, &1_000);
+        treasury.deposit(&member1, &1_000);
+        treasury.deposit(&member2, &1_000);
+
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        client.set_execution_delay(&admin, &100);
+
+        let proposal_id = client.create_proposal(
+            &member3,
+            &symbol_short!("fund"),
+            &symbol_short!("grant"),
+            &ProposalAction::Funding,
+            &1_500,
+            &member3,
+        );
+
+        // Ragequit needs a passed Funding proposal inside its timelock.
+        assert_eq!(
+            client.try_ragequit(&member1, &proposal_id, &500),
+            Err(Ok(Error::RagequitWindowClosed))
+        );
+
+        client.vote(&member1, &proposal_id, &false);
+        client.vote(&member2, &proposal_id, &true);
+        client.vote(&member3, &proposal_id, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &proposal_id);
+
+        // The dissenter exits with a pro-rata slice.
+        assert_eq!(client.ragequit(&member1, &proposal_id, &500), 500);
+        let equity = treasury.get_member_equity(&member1);
+        assert_eq!(equity.shares, 500);
+        assert_eq!(treasury.get_balance(), 1_500);
+        assert_eq!(
+            soroban_sdk::token::Client::new(&env, &asset).balance(&member1),
+            500
+        );
+
+        // A yes voter on the still-pending proposal is blocked, and the
+        // treasury checks the member's share balance.
+        assert_eq!(
+            client.try_ragequit(&member2, &proposal_id, &100),
+            Err(Ok(Error::VotedForPendingProposal))
+        );
+        assert_eq!(
+            client.try_ragequit(&member1, &proposal_id, &501),
+            Err(Ok(Error::RagequitFailed))
+        );
+
+        // Once the timelock ends the window closes and the proposal executes.
+        env.ledger().with_mut(|l| l.sequence_number += 100);
+        assert_eq!(
+            client.try_ragequit(&member1, &proposal_id, &100),
+            Err(Ok(Error::RagequitWindowClosed))
+        );
+        client.execute_proposal(&member3, &proposal_id);
+    }
+
+    #[test]
+    fn test_ragequit_is_released_when_yes_votes_settle() {
+        let (env, admin, acl_id, _, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &1,
+            &Vec::from_array(&env, [client.address.clone()]),
+            &acl_id,
+        );
+        treasury.set_share_minting(&admin, &true);
+        register_governance(&treasury, &client.address, &client.address);
+        sac.mint(&member2, &1_000);
+        treasury.deposit(&member2, &1_000);
+
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        client.set_execution_delay(&admin, &100);
+
+        // A passed General proposal nobody executes, and a passed Funding
+        // proposal, both with member2 voting For.
+        let general = client.create_proposal(
+            &member3,
+            &symbol_short!("note"),
+            &symbol_short!("memo"),
+            &ProposalAction::General,
+            &0,
+            &member3,
+        );
+        let funding = client.create_proposal(
+            &member3,
+            &symbol_short!("fund"),
+            &symbol_short!("grant"),
+            &ProposalAction::Funding,
+            &100,
+            &member3,
+        );
+        for id in [general, funding] {
+            client.vote(&member2, &id, &true);
+            client.vote(&member3, &id, &true);
+        }
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &general);
+        client.finalize(&member1, &funding);
+
+        // The unexecuted Funding proposal blocks member2.
+        assert_eq!(
+            client.try_ragequit(&member2, &funding, &100),
+            Err(Ok(Error::VotedForPendingProposal))
+        );
+
+        env.ledger().with_mut(|l| l.sequence_number += 100);
+        client.execute_proposal(&member3, &funding);
+
+        // With the transfer made, only the passed General proposal is left,
+        // and it does not block an exit during the next Funding timelock.
+        let next = client.create_proposal(
+            &member3,
+            &symbol_short!("fund"),
+            &symbol_short!("grant"),
+            &ProposalAction::Funding,
+            &100,
+            &member3,
+        );
+        client.vote(&member1, &next, &true);
+        client.vote(&member2, &next, &false);
+        client.vote(&member3, &next, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &next);
+        assert_eq!(client.ragequit(&member2, &next, &100), 100);
+    }
+
+    #[test]
+    fn test_grant_proposal_escrows_milestones_and_clawback_vote() {
+        let (env, admin, acl_id, _, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &1,
+            &Vec::from_array(&env, [client.address.clone()]),
+            &acl_id,
+        );
+        register_governance(&treasury, &client.address, &client.address);
+        sac.mint(&member1, &1_000);
+        treasury.deposit(&member1, &1_000);
+
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        let recipient = Address::generate(&env);
+        let terms = GrantTerms {
+            reviewers: Vec::from_array(&env, [member2.clone()]),
+            reviewer_threshold: 1,
+            milestones: Vec::from_array(
+                &env,
+                [
+                    MilestoneTerms {
+                        amount: 400,
+                        deadline: 10_000,
+                    },
+                    MilestoneTerms {
+                        amount: 300,
+                        deadline: 10_000,
+                    },
+                ],
+            ),
+        };
+        let proposal_id = client.create_grant_proposal(
+            &member1,
+            &symbol_short!("grant"),
+            &symbol_short!("audit"),
+            &recipient,
+            &terms,
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.action, ProposalAction::Funding);
+        assert_eq!(proposal.amount, 700);
+
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &proposal_id);
+        client.execute_proposal(&member1, &proposal_id);
+
+        // Governance is the treasury's only signer, so its proposal is approved.
+        let grant_id = client.get_proposal(&proposal_id).treasury_tx_id.unwrap();
+        treasury.execute(&client.address, &grant_id);
+        assert_eq!(treasury.get_grant(&grant_id).recipient, recipient);
+        assert_eq!(treasury.get_balance(), 300);
+
+        treasury.approve_milestone(&member2, &grant_id, &0);
+        assert_eq!(
+            soroban_sdk::token::Client::new(&env, &asset).balance(&recipient),
+            400
+        );
+
+        // A clawback vote returns the unreleased milestone.
+        let clawback_id = client.create_clawback_proposal(
+            &member2,
+            &symbol_short!("clawback"),
+            &symbol_short!("stalled"),
+            &grant_id,
+        );
+        client.vote(&member1, &clawback_id, &true);
+        client.vote(&member2, &clawback_id, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member2, &clawback_id);
+        client.execute_proposal(&member2, &clawback_id);
+        assert_eq!(treasury.get_balance(), 600);
+        assert_eq!(
+            treasury
+                .get_grant(&grant_id)
+                .milestones
+                .get(1)
+                .unwrap()
+                .status,
+            MilestoneStatus::ClawedBack
+        );
+
+        // Nothing is left to claw back.
+        let again = client.create_clawback_proposal(
+            &member2,
+            &symbol_short!("clawback"),
+            &symbol_short!("again"),
+            &grant_id,
+        );
+        client.vote(&member2, &again, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member2, &again);
+        assert_eq!(
+            client.try_execute_proposal(&member2, &again),
+            Err(Ok(Error::ClawbackFailed))
+        );
+    }
+
+    #[test]
+    fn test_funding_proposal_with_explicit_request_links_treasury_tx() {
+        let (env, admin, acl_id, _, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &2,
+            &Vec::from_array(&env, [client.address.clone(), admin.clone()]),
+            &acl_id,
+        );
+        sac.mint(&member1, &1_000);
+        treasury.deposit(&member1, &1_000);
+
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        let recipient = Address::generate(&env);
+        let request = FundingRequest {
+            recipient: recipient.clone(),
+            asset: asset.clone(),
+            expires_after: 3_600,
+            memo: symbol_short!("audit_q3"),
+            category: symbol_short!("security"),
+        };
+        assert_eq!(
+            client.try_create_funding_proposal(
+                &member1,
+                &symbol_short!("audit"),
+                &symbol_short!("q3"),
+                &0,
+                &request,
+            ),
+            Err(Ok(Error::InvalidProposal))
+        );
+        let proposal_id = client.create_funding_proposal(
+            &member1,
+            &symbol_short!("audit"),
+            &symbol_short!("q3"),
+            &600,
+            &request,
+        );
+        assert_eq!(
+            client.get_funding_request(&proposal_id),
+            Some(request.clone())
+        );
+        assert_eq!(client.get_proposal(&proposal_id).target, member1);
+
+        // A request for another asset cannot execute.
+        let other_asset = Address::generate(&env);
+        let mismatched = client.create_funding_proposal(
+            &member1,
+            &symbol_short!("audit"),
+            &symbol_short!("other"),
+            &600,
+            &FundingRequest {
+                asset: other_asset,
+                ..request
+            },
+        );
+
+        client.vote(&member1, &proposal_id, &true);
+        client.vote(&member1, &mismatched, &true);
+        env.ledger().with_mut(|l| {
+            l.sequence_number += 11;
+            l.timestamp = 1_000;
+        });
+        client.finalize(&member1, &proposal_id);
+        client.finalize(&member1, &mismatched);
+        assert_eq!(
+            client.try_execute_proposal(&member1, &mismatched),
+            Err(Ok(Error::AssetMismatch))
+        );
+        client.execute_proposal(&member1, &proposal_id);
+
+        let tx_id = client.get_proposal(&proposal_id).treasury_tx_id.unwrap();
+        let tx = treasury.get_transaction(&tx_id);
+        assert_eq!(tx.to, recipient);
+        assert_eq!(tx.amount, 600);
+        assert_eq!(tx.memo, symbol_short!("audit_q3"));
+        assert_eq!(tx.expires_at, 4_600);
+        assert_eq!(client.get_proposal(&mismatched).treasury_tx_id, None);
+    }
+
+    #[test]
+    fn test_sync_funding_status_and_retry_after_failure() {
+        let (env, admin, acl_id, _, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        let signer = Address::generate(&env);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &2,
+            &Vec::from_array(&env, [client.address.clone(), signer.clone()]),
+            &acl_id,
+        );
+        sac.mint(&member1, &1_000);
+        treasury.deposit(&member1, &1_000);
+
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        let proposal_id = client.create_proposal(
+            &member1,
+            &symbol_short!("fund"),
+            &symbol_short!("tools"),
+            &ProposalAction::Funding,
+            &400,
+            &member1,
+        );
+        assert_eq!(
+            client.try_sync_funding_status(&proposal_id),
+            Err(Ok(Error::FundingNotPending))
+        );
+        client.vote(&member1, &proposal_id, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &proposal_id);
+        client.execute_proposal(&member1, &proposal_id);
+
+        // Still open in the treasury.
+        assert_eq!(
+            client.sync_funding_status(&proposal_id),
+            ProposalStatus::Executed
+        );
+
+        // The treasury transaction expires unexecuted.
+        env.ledger().with_mut(|l| l.timestamp += 7 * 24 * 3600 + 1);
+        assert_eq!(
+            client.sync_funding_status(&proposal_id),
+            ProposalStatus::FundingFailed
+        );
+        let first_tx = client.get_proposal(&proposal_id).treasury_tx_id.unwrap();
+
+        // A failed proposal can be executed again, queueing a new transaction.
+        client.execute_proposal(&member1, &proposal_id);
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        let second_tx = proposal.treasury_tx_id.unwrap();
+        assert_ne!(first_tx, second_tx);
+
+        treasury.approve(&signer, &second_tx);
+        treasury.execute(&signer, &second_tx);
+        assert_eq!(
+            client.sync_funding_status(&proposal_id),
+            ProposalStatus::Funded
+        );
+        assert_eq!(
+            client.try_execute_proposal(&member1, &proposal_id),
+            Err(Ok(Error::ProposalRejected))
+        );
+        assert_eq!(
+            client.try_sync_funding_status(&proposal_id),
+            Err(Ok(Error::FundingNotPending))
+        );
+    }
+
+    #[test]
+    fn test_proposal_bond_returned_on_pass_and_slashed_otherwise() {
+        let (env, admin, acl_id, _, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+        let token_client = soroban_sdk::token::Client::new(&env, &asset);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &1,
+            &Vec::from_array(&env, [admin.clone()]),
+            &acl_id,
+        );
+        register_governance(&treasury, &admin, &client.address);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+            sac.mint(&m, &1_000);
+        }
+
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        // The bond is set by a PolicyChange vote.
+        assert_eq!(
+            client.try_create_policy_proposal(
+                &member1,
+                &symbol_short!("bond"),
+                &symbol_short!("spam"),
+                &PolicyParam::ProposalBond(-1),
+            ),
+            Err(Ok(Error::InvalidProposal))
+        );
+        let policy_id = client.create_policy_proposal(
+            &member1,
+            &symbol_short!("bond"),
+            &symbol_short!("spam"),
+            &PolicyParam::ProposalBond(100),
+        );
+        client.vote(&member1, &policy_id, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &policy_id);
+        client.execute_proposal(&member1, &policy_id);
+        assert_eq!(client.get_config().proposal_bond, 100);
+
+        // Passed: the bond comes back.
+        let passed = client.create_proposal(
+            &member2,
+            &symbol_short!("a"),
+            &symbol_short!("a"),
+            &ProposalAction::General,
+            &0,
+            &member2,
+        );
+        assert_eq!(client.get_proposal(&passed).bond, 100);
+        assert_eq!(token_client.balance(&member2), 900);
+        assert_eq!(token_client.balance(&client.address), 100);
+        client.vote(&member1, &passed, &true);
+
+        // Rejected and quorum-failed proposals lose the bond to the treasury.
+        let rejected = client.create_proposal(
+            &member3,
+            &symbol_short!("b"),
+            &symbol_short!("b"),
+            &ProposalAction::General,
+            &0,
+            &member3,
+        );
+        client.vote(&member1, &rejected, &false);
+        let expired = client.create_proposal(
+            &member3,
+            &symbol_short!("c"),
+            &symbol_short!("c"),
+            &ProposalAction::General,
+            &0,
+            &member3,
+        );
+
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        assert_eq!(client.finalize(&member1, &passed), ProposalStatus::Passed);
+        assert_eq!(
+            client.finalize(&member1, &rejected),
+            ProposalStatus::Rejected
+        );
+        assert_eq!(client.finalize(&member1, &expired), ProposalStatus::Expired);
+
+        assert_eq!(token_client.balance(&member2), 1_000);
+        assert_eq!(token_client.balance(&member3), 800);
+        assert_eq!(token_client.balance(&client.address), 0);
+        assert_eq!(treasury.get_balance(), 200);
+        assert_eq!(treasury.get_member_account(&client.address).contributed, 0);
+    }
+
+    #[test]
+    fn test_cancel_proposal_by_proposer_and_admin() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        let create = |proposer: &Address| {
+            client.create_proposal(
+                proposer,
+                &symbol_short!("t"),
+                &symbol_short!("t"),
+                &ProposalAction::General,
+                &0,
+                proposer,
+            )
+        };
+        let reason = symbol_short!("dup");
+
+        // The proposer can withdraw before quorum, others cannot cancel.
+        let withdrawn = create(&member1);
+        assert_eq!(
+            client.try_cancel_proposal(&member2, &withdrawn, &reason),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.cancel_proposal(&member1, &withdrawn, &reason);
+        assert_eq!(
+            client.get_proposal(&withdrawn).status,
+            ProposalStatus::Canceled
+        );
+        assert_eq!(
+            client.try_vote(&member2, &withdrawn, &true),
+            Err(Ok(Error::VotingClosed))
+        );
+
+        // Once quorum is reached only the admin can cancel.
+        let reached = create(&member1);
+        client.vote(&member2, &reached, &false);
+        assert_eq!(
+            client.try_cancel_proposal(&member1, &reached, &reason),
+            Err(Ok(Error::CancelNotAllowed))
+        );
+        client.cancel_proposal(&admin, &reached, &symbol_short!("abuse"));
+        assert_eq!(
+            client.get_proposal(&reached).status,
+            ProposalStatus::Canceled
+        );
+
+        // Canceled proposals stay out of the quorum statistics.
+        let finalized = create(&member2);
+        let unsupported = create(&member1);
+        client.vote(&member1, &finalized, &true);
+        client.vote(&member3, &finalized, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &finalized);
+
+        // Once voting ends without quorum, withdrawing can no longer dodge
+        // finalization, and the admin cannot cancel a finished vote either.
+        assert_eq!(
+            client.try_cancel_proposal(&member1, &unsupported, &reason),
+            Err(Ok(Error::VotingClosed))
+        );
+        assert_eq!(
+            client.try_cancel_proposal(&admin, &finalized, &reason),
+            Err(Ok(Error::VotingClosed))
+        );
+        assert_eq!(
+            client.finalize(&member1, &unsupported),
+            ProposalStatus::Expired
+        );
+        assert_eq!(
+            client.try_finalize(&member1, &reached),
+            Err(Ok(Error::VotingClosed))
+        );
+
+        let stats = client.get_quorum_stats();
+        assert_eq!(stats.finalized, 2);
+        assert_eq!(stats.quorum_reached, 1);
+        assert_eq!(stats.votes_cast, 2);
+        assert_eq!(stats.eligible_votes, 6);
+        assert_eq!(stats.canceled, 2);
+    }
+
+    #[test]
+    fn test_action_rules_are_fixed_at_creation() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        let supermajority = ActionRules {
+            quorum_percent: 100,
+            approval_percent: 75,
+            voting_period: 20,
+        };
+        assert_eq!(
+            client.try_set_action_rules(
+                &admin,
+                &ProposalAction::RemoveMember,
+                &ActionRules {
+                    approval_percent: 101,
+                    ..supermajority.clone()
+                },
+            ),
+            Err(Ok(Error::InvalidRules))
+        );
+        client.set_action_rules(&admin, &ProposalAction::RemoveMember, &supermajority);
+        assert_eq!(
+            client.get_action_rules(&ProposalAction::RemoveMember),
+            supermajority
+        );
+        assert_eq!(
+            client
+                .get_action_rules(&ProposalAction::General)
+                .approval_percent,
+            50
+        );
+
+        let removal = client.create_proposal(
+            &member1,
+            &symbol_short!("rm"),
+            &symbol_short!("m3"),
+            &ProposalAction::RemoveMember,
+            &0,
+            &member3,
+        );
+        let signal = client.create_proposal(
+            &member1,
+            &symbol_short!("sig"),
+            &symbol_short!("sig"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+        );
+        let removal_proposal = client.get_proposal(&removal);
+        assert_eq!(removal_proposal.rules, supermajority);
+        assert_eq!(removal_proposal.ends_at, removal_proposal.created_at + 20);
+
+        // Loosening the rules later does not affect the in-flight vote.
+        client.set_action_rules(
+            &admin,
+            &ProposalAction::RemoveMember,
+            &ActionRules {
+                quorum_percent: 50,
+                approval_percent: 50,
+                voting_period: 10,
+            },
+        );
+
+        for id in [removal, signal] {
+            client.vote(&member1, &id, &true);
+            client.vote(&member2, &id, &true);
+            client.vote(&member3, &id, &false);
+        }
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        assert_eq!(client.finalize(&member1, &signal), ProposalStatus::Passed);
+        assert_eq!(
+            client.try_finalize(&member1, &removal),
+            Err(Ok(Error::VotingStillActive))
+        );
+        env.ledger().with_mut(|l| l.sequence_number += 10);
+        // 2 of 3 is below the 75% supermajority.
+        assert_eq!(
+            client.finalize(&member1, &removal),
+            ProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_timestamp_voting_clock_keeps_ledger_proposals_finalizable() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+        let create = || {
+            client.create_proposal(
+                &member1,
+                &symbol_short!("t"),
+                &symbol_short!("t"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+            )
+        };
+        let by_ledger = create();
+        client.vote(&member1, &by_ledger, &true);
+
+        client.set_voting_clock(&admin, &VotingClock::Timestamp, &3_600);
+        assert_eq!(client.get_config().voting_clock, VotingClock::Timestamp);
+        let by_time = create();
+        let proposal = client.get_proposal(&by_time);
+        assert_eq!(proposal.clock, VotingClock::Timestamp);
+        assert_eq!(proposal.created_time, 1_000);
+        assert_eq!(proposal.ends_time, 4_600);
+        client.vote(&member1, &by_time, &true);
+
+        // Ledgers pass but little time does: only the ledger proposal closes.
+        env.ledger().with_mut(|l| {
+            l.sequence_number += 11;
+            l.timestamp += 60;
+        });
+        assert_eq!(
+            client.finalize(&member1, &by_ledger),
+            ProposalStatus::Passed
+        );
+        assert_eq!(
+            client.try_finalize(&member1, &by_time),
+            Err(Ok(Error::VotingStillActive))
+        );
+
+        // The timelock runs on each proposal's own clock.
+        client.set_execution_delay(&admin, &600);
+        env.ledger().with_mut(|l| l.timestamp = 4_601);
+        assert_eq!(client.finalize(&member1, &by_time), ProposalStatus::Passed);
+        let proposal = client.get_proposal(&by_time);
+        assert_eq!(proposal.executable_time, 5_201);
+        assert_eq!(proposal.executable_at, 0);
+        env.ledger().with_mut(|l| l.sequence_number += 1_000);
+        assert_eq!(
+            client.try_execute_proposal(&member1, &by_time),
+            Err(Ok(Error::TimelockActive))
+        );
+        env.ledger().with_mut(|l| l.timestamp = 5_201);
+        client.execute_proposal(&member1, &by_time);
+        assert_eq!(
+            client.get_proposal(&by_time).status,
+            ProposalStatus::Executed
+        );
+    }
+
+    #[test]
+    fn test_review_delay_holds_voting_until_start() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
+
+        let member1 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone()]);
+        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        client.set_review_delay(&admin, &5);
+        assert_eq!(client.get_config().review_delay, 5);
+
+        let start = env.ledger().sequence();
+        let proposal_id = client.create_proposal(
+            &member1,
+            &symbol_short!("t"),
+            &symbol_short!("t"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+        );
+        let proposal = client.get_proposal(&proposal_id);
+        assert_eq!(proposal.status, ProposalStatus::Pending);
+        assert_eq!(proposal.voting_starts_at, start as u64 + 5);
+        assert_eq!(proposal.ends_at, start + 15);
+        assert_eq!(
+            client.try_vote(&member1, &proposal_id, &true),
+            Err(Ok(Error::VotingNotStarted))
+        );
+
+        env.ledger().with_mut(|l| l.sequence_number += 5);
+        assert_eq!(
+            client.get_proposal(&proposal_id).status,
+            ProposalStatus::Active
+        );
+        client.vote(&member1, &proposal_id, &true);
+
+        // The full voting period runs from the start, not from creation.
+        env.ledger().with_mut(|l| l.sequence_number += 5);
+        assert_eq!(
+            client.try_finalize(&member1, &proposal_id),
+            Err(Ok(Error::VotingStillActive))
+        );
+        env.ledger().with_mut(|l| l.sequence_number += 6);
+        assert_eq!(
+            client.finalize(&member1, &proposal_id),
+            ProposalStatus::Passed
+        );
+    }
+
+    #[test]
+    fn test_multi_choice_tallies_and_signer_election() {
+        let (env, admin, acl_id, _, client) = setup_contract();
+
+        let voters: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+        let mut members = Vec::new(&env);
+        for m in voters.iter() {
+            assign_role(&env, &acl_id, &admin, m, &Role::Member);
+            members.push_back(m.clone());
+        }
+
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &1,
+            &Vec::from_array(&env, [admin.clone()]),
+            &acl_id,
+        );
+        register_governance(&treasury, &admin, &client.address);
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        // Plurality: a tie for first place rejects the proposal.
+        let vendors = client.create_choice_proposal(
+            &voters[0],
+            &symbol_short!("vendor"),
+            &symbol_short!("audit"),
+            &TallyMethod::Plurality,
+            &Vec::from_array(
+                &env,
+                [symbol_short!("a"), symbol_short!("b"), symbol_short!("c")],
+            ),
+        );
+        assert_eq!(
+            client.try_vote(&voters[0], &vendors, &true),
+            Err(Ok(Error::InvalidBallot))
+        );
+        assert_eq!(
+            client.try_vote_choice(&voters[0], &vendors, &vec![&env, 0, 1]),
+            Err(Ok(Error::InvalidBallot))
+        );
+        assert_eq!(
+            client.try_vote_choice(&voters[0], &vendors, &vec![&env, 3]),
+            Err(Ok(Error::InvalidBallot))
+        );
+        for (voter, option) in voters.iter().zip([0u32, 0, 1, 1, 2]) {
+            client.vote_choice(voter, &vendors, &vec![&env, option]);
+        }
+        assert_eq!(
+            client.try_vote_choice(&voters[0], &vendors, &vec![&env, 2]),
+            Err(Ok(Error::AlreadyVoted))
+        );
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Admin, &new_admin);
+        // Instant runoff: the same first preferences elect candidate 0 once
+        // candidate 1 is eliminated and its ballot transfers.
+        let candidates: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+        assert_eq!(
+            client.try_create_signer_election(
+                &voters[0],
+                &symbol_short!("signer"),
+                &symbol_short!("seat"),
+                &TallyMethod::InstantRunoff,
+                &vec![
+                    &env,
+                    candidates[0].clone(),
+                    candidates[1].clone(),
+                    candidates[0].clone()
+                ],
+            ),
+            Err(Ok(Error::InvalidProposal))
+        );
+        let election = client.create_signer_election(
+            &voters[0],
+            &symbol_short!("signer"),
+            &symbol_short!("seat"),
+            &TallyMethod::InstantRunoff,
+            &Vec::from_slice(&env, &candidates),
+        );
+        let rankings = [
+            vec![&env, 0u32, 1],
+            vec![&env, 0],
+            vec![&env, 1, 0],
+            vec![&env, 2, 1],
+            vec![&env, 2],
+        ];
+        for (voter, ranking) in voters.iter().zip(rankings) {
+            client.vote_choice(voter, &election, &ranking);
+        }
+        assert_eq!(
+            client.get_choice(&election).unwrap().tallies,
+            vec![&env, 2u32, 1, 2]
+        );
 
-        env.events().publish(
-            (symbol_short!("gov"), symbol_short!("admin")),
-            (current_admin, new_admin.clone()),
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        assert_eq!(
+            client.finalize(&voters[0], &vendors),
+            ProposalStatus::Rejected
         );
+        assert_eq!(client.get_choice(&vendors).unwrap().winner, None);
+        assert_eq!(
+            client.finalize(&voters[0], &election),
+            ProposalStatus::Passed
+        );
+        assert_eq!(client.get_choice(&election).unwrap().winner, Some(0));
 
-        Ok(())
+        client.execute_proposal(&voters[0], &election);
+        assert!(treasury.get_signers().contains(&candidates[0]));
+        assert!(!treasury.get_signers().contains(&candidates[2]));
     }
 
-    /// Update the quorum percentage. Admin only.
-    pub fn set_quorum(env: Env, admin: Address, new_quorum: u32) -> Result<(), Error> {
-        Self::require_initialized(&env)?;
-        Self::require_admin(&env, &admin)?;
-
-        admin.require_auth();
+    #[test]
+    fn test_quadratic_votes_spend_per_period_credits() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(2), 1);
+        assert_eq!(isqrt(99), 9);
+        assert_eq!(isqrt(100), 10);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
 
-        env.storage()
-            .instance()
-            .set(&DataKey::QuorumPercent, &new_quorum);
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
 
-        env.events().publish(
-            (symbol_short!("gov"), symbol_short!("quorum")),
-            new_quorum,
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        client.set_credit_policy(
+            &admin,
+            &CreditPolicy {
+                allotment: 100,
+                period: 1_000,
+            },
+        );
+        client.set_member_credits(&admin, &member2, &Some(200));
+        client.set_voting_mode(&admin, &VotingMode::Quadratic);
+        assert_eq!(client.get_config().voting_mode, VotingMode::Quadratic);
+
+        let create = || {
+            client.create_proposal(
+                &member1,
+                &symbol_short!("t"),
+                &symbol_short!("t"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+            )
+        };
+        let first = create();
+        assert_eq!(
+            client.try_vote(&member1, &first, &true),
+            Err(Ok(Error::InvalidBallot))
+        );
+        assert_eq!(
+            client.try_vote_quadratic(&member1, &first, &true, &101),
+            Err(Ok(Error::InsufficientCredits))
+        );
+        assert_eq!(client.vote_quadratic(&member1, &first, &true, &100), 10);
+        assert_eq!(client.vote_quadratic(&member2, &first, &false, &196), 14);
+        assert_eq!(client.vote_quadratic(&member3, &first, &true, &16), 4);
+        let proposal = client.get_proposal(&first);
+        assert_eq!(
+            (
+                proposal.votes_for,
+                proposal.votes_against,
+                proposal.total_votes
+            ),
+            (14, 14, 3)
         );
+        assert_eq!(client.get_voice_credits(&member2), 4);
 
-        Ok(())
+        // Credits are spent for the period, then refill.
+        let second = create();
+        assert_eq!(
+            client.try_vote_quadratic(&member1, &second, &true, &1),
+            Err(Ok(Error::InsufficientCredits))
+        );
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        assert_eq!(client.finalize(&member1, &first), ProposalStatus::Rejected);
+
+        env.ledger().with_mut(|l| l.sequence_number = 1_000);
+        let third = create();
+        assert_eq!(client.get_voice_credits(&member1), 100);
+        assert_eq!(client.vote_quadratic(&member1, &third, &true, &50), 7);
+
+        // Proposals keep the mode they were created with.
+        client.set_voting_mode(&admin, &VotingMode::OneMemberOneVote);
+        assert_eq!(
+            client.try_vote(&member2, &third, &true),
+            Err(Ok(Error::InvalidBallot))
+        );
     }
 
-    /// Upgrade contract WASM. Admin only.
-    pub fn upgrade(
-        env: Env,
-        admin: Address,
-        new_wasm_hash: soroban_sdk::BytesN<32>,
-    ) -> Result<(), Error> {
-        Self::require_initialized(&env)?;
-        Self::require_admin(&env, &admin)?;
+    #[test]
+    fn test_conviction_request_passes_into_treasury_withdrawal() {
+        let (env, admin, acl_id, _, client) = setup_contract();
 
-        admin.require_auth();
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
-        Ok(())
-    }
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &1,
+            &Vec::from_array(&env, [client.address.clone()]),
+            &acl_id,
+        );
+        sac.mint(&member1, &1_000);
+        treasury.deposit(&member1, &1_000);
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        let recipient = Address::generate(&env);
+        let small =
+            client.create_conviction_request(&member1, &recipient, &50, &symbol_short!("meetup"));
+        let large =
+            client.create_conviction_request(&member1, &recipient, &250, &symbol_short!("offsite"));
+
+        // 5% of the balance: 2_000 * 300 / (0.001 * 0.15^2) ppm-scaled.
+        assert_eq!(client.get_conviction_threshold(&small), Some(26_666));
+        // Asking for 25% is above the 20% max ratio.
+        assert_eq!(client.get_conviction_threshold(&large), None);
+
+        client.stake_support(&member1, &small, &100);
+        client.stake_support(&member2, &small, &100);
+        assert_eq!(
+            client.try_stake_support(&member1, &large, &1),
+            Err(Ok(Error::SupportExceeded))
+        );
 
-    // ========================================================================
-    // Internal Helpers
-    // ========================================================================
+        // Conviction builds toward 200 / 0.001 and crosses after ~143 ledgers.
+        env.ledger().with_mut(|l| l.sequence_number += 100);
+        let request = client.get_conviction_request(&small);
+        assert!(request.conviction > 18_000 && request.conviction < 26_666);
+        assert_eq!(
+            client.try_check_conviction(&small),
+            Err(Ok(Error::ConvictionBelowThreshold))
+        );
+        env.ledger().with_mut(|l| l.sequence_number += 50);
+        let tx_id = client.check_conviction(&small);
+        let request = client.get_conviction_request(&small);
+        assert_eq!(request.status, ConvictionStatus::Passed);
+        assert_eq!(request.treasury_tx_id, Some(tx_id));
+        assert_eq!(
+            client.try_check_conviction(&small),
+            Err(Ok(Error::ConvictionClosed))
+        );
 
-    fn require_initialized(env: &Env) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(Error::NotInitialized);
-        }
-        Ok(())
-    }
+        treasury.execute(&client.address, &tx_id);
+        assert_eq!(
+            soroban_sdk::token::Client::new(&env, &asset).balance(&recipient),
+            50
+        );
 
-    fn get_acl(env: &Env) -> Result<AccessControlContractClient, Error> {
-        let acl_address: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::AclAddress)
-            .ok_or(Error::NotInitialized)?;
-        Ok(AccessControlContractClient::new(env, &acl_address))
-    }
+        // Stakes on a passed request are freed for other requests.
+        assert_eq!(
+            client.try_stake_support(&member3, &small, &10),
+            Err(Ok(Error::ConvictionClosed))
+        );
+        client.stake_support(&member1, &small, &0);
+        client.stake_support(&member1, &large, &100);
+        client.withdraw_conviction_request(&member1, &large);
+        assert_eq!(
+            client.get_conviction_request(&large).status,
+            ConvictionStatus::Withdrawn
+        );
 
-    fn require_acl_admin_or_above(env: &Env, caller: &Address) -> Result<(), Error> {
-        let acl = Self::get_acl(env)?;
-        if !acl.is_admin_or_above(caller) {
-            return Err(Error::Unauthorized);
-        }
-        Ok(())
-    }
+        // Removing a member withdraws their support from every request.
+        let next =
+            client.create_conviction_request(&member1, &recipient, &50, &symbol_short!("next"));
+        client.stake_support(&member2, &small, &0);
+        client.stake_support(&member2, &next, &100);
+        client.stake_support(&member3, &next, &40);
+        assert_eq!(client.get_conviction_request(&next).support, 140);
 
-    fn require_acl_member_or_above(env: &Env, caller: &Address) -> Result<(), Error> {
-        let acl = Self::get_acl(env)?;
-        if !acl.is_member_or_above(caller) {
-            return Err(Error::Unauthorized);
-        }
-        Ok(())
+        let removal = client.create_proposal(
+            &member1,
+            &symbol_short!("rm"),
+            &symbol_short!("m2"),
+            &ProposalAction::RemoveMember,
+            &0,
+            &member2,
+        );
+        client.vote(&member1, &removal, &true);
+        client.vote(&member3, &removal, &true);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &removal);
+        client.execute_proposal(&member1, &removal);
+
+        let request = client.get_conviction_request(&next);
+        assert_eq!(request.support, 40);
+        assert!(request.conviction > 0);
     }
 
-    fn require_member(env: &Env, caller: &Address) -> Result<(), Error> {
-        let members: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&DataKey::Members)
-            .unwrap_or(Vec::new(env));
+    #[test]
+    fn test_optimistic_proposal_passes_unless_vetoed() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
 
-        let mut is_member = false;
-        for i in 0..members.len() {
-            if members.get(i).unwrap() == *caller {
-                is_member = true;
-                break;
-            }
-        }
-        if !is_member {
-            return Err(Error::NotAMember);
+        let voters: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+        let mut members = Vec::new(&env);
+        for m in voters.iter() {
+            assign_role(&env, &acl_id, &admin, m, &Role::Member);
+            members.push_back(m.clone());
         }
-        Self::require_acl_member_or_above(env, caller)
-    }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        client.set_veto_percent(&admin, &40);
+
+        let newcomer = Address::generate(&env);
+        let renew = client.create_optimistic_proposal(
+            &voters[0],
+            &symbol_short!("renew"),
+            &symbol_short!("c"),
+            &ProposalAction::AddMember,
+            &0,
+            &newcomer,
+        );
+        let config = client.create_optimistic_proposal(
+            &voters[0],
+            &symbol_short!("cfg"),
+            &symbol_short!("c"),
+            &ProposalAction::General,
+            &0,
+            &voters[0],
+        );
+        assert_eq!(client.get_veto_percent(&renew), Some(40));
+        assert_eq!(
+            client.try_vote(&voters[1], &renew, &true),
+            Err(Ok(Error::InvalidBallot))
+        );
 
-    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-        if *caller != admin {
-            return Err(Error::Unauthorized);
+        // Two of five vetoes (40%) do not exceed the threshold; three do.
+        for voter in &voters[1..3] {
+            client.veto(voter, &renew);
         }
-        Self::require_acl_admin_or_above(env, caller)
-    }
-}
-
-// ============================================================================
-// Tests
-// ============================================================================
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::Env;
-    use stellar_sentinel_access_control::{
-        AccessControlContract, AccessControlContractClient, Role,
-    };
+        for voter in &voters[1..4] {
+            client.veto(voter, &config);
+        }
+        assert_eq!(
+            client.try_veto(&voters[1], &renew),
+            Err(Ok(Error::AlreadyVoted))
+        );
 
-    fn deploy_acl(env: &Env, owner: &Address) -> Address {
-        let acl_id = env.register_contract(None, AccessControlContract);
-        let acl_client = AccessControlContractClient::new(env, &acl_id);
-        acl_client.initialize(owner);
-        acl_id
-    }
+        env.ledger().with_mut(|l| l.sequence_number += 11);
 
-    fn assign_role(env: &Env, acl_id: &Address, admin: &Address, target: &Address, role: &Role) {
-        let acl_client = AccessControlContractClient::new(env, acl_id);
-        acl_client.assign_role(admin, target, role);
-    }
+        // No quorum needed: executing an unvetoed proposal settles it.
+        client.execute_proposal(&voters[0], &renew);
+        assert_eq!(client.get_proposal(&renew).status, ProposalStatus::Executed);
+        assert!(client.get_members().contains(&newcomer));
 
-    fn setup_contract() -> (Env, Address, Address, Address, GovernanceContractClient<'static>) {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, GovernanceContract);
-        let client = GovernanceContractClient::new(&env, &contract_id);
-        let admin = Address::generate(&env);
-        let acl_id = deploy_acl(&env, &admin);
-        let treasury_id = Address::generate(&env);
-        (env, admin, acl_id, treasury_id, client)
+        assert_eq!(client.finalize(&voters[0], &config), ProposalStatus::Vetoed);
+        assert_eq!(
+            client.try_execute_proposal(&voters[0], &config),
+            Err(Ok(Error::ProposalRejected))
+        );
+        assert_eq!(client.get_quorum_stats().finalized, 0);
     }
 
     #[test]
-    fn test_initialize() {
+    fn test_vote_by_sig_relays_signed_votes_with_nonces() {
+        use ed25519_dalek::{Signer, SigningKey};
+
         let (env, admin, acl_id, treasury_id, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        let member3 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
 
-        client.initialize(&admin, &members, &50, &1000, &acl_id, &treasury_id);
+        let key1 = SigningKey::from_bytes(&[1; 32]);
+        let key2 = SigningKey::from_bytes(&[2; 32]);
+        client.register_vote_key(
+            &member1,
+            &BytesN::from_array(&env, &key1.verifying_key().to_bytes()),
+        );
+        client.register_vote_key(
+            &member2,
+            &BytesN::from_array(&env, &key2.verifying_key().to_bytes()),
+        );
 
-        let config = client.get_config();
-        assert_eq!(config.admin, admin);
-        assert_eq!(config.member_count, 2);
-        assert_eq!(config.quorum_percent, 50);
-        assert_eq!(config.voting_period, 1000);
+        let sign =
+            |key: &SigningKey, voter: &Address, proposal_id: u64, vote_for: bool, nonce: u64| {
+                let payload = client.vote_payload(voter, &proposal_id, &vote_for, &nonce);
+                let mut message = std::vec![0u8; payload.len() as usize];
+                payload.copy_into_slice(&mut message);
+                BytesN::from_array(&env, &key.sign(&message).to_bytes())
+            };
+
+        let create = || {
+            client.create_proposal(
+                &member1,
+                &symbol_short!("t"),
+                &symbol_short!("t"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+            )
+        };
+        let first = create();
+        let second = create();
+
+        // A relayer submits both members' votes in one call.
+        let batch = Vec::from_array(
+            &env,
+            [
+                SignedVote {
+                    voter: member1.clone(),
+                    proposal_id: first,
+                    vote_for: true,
+                    nonce: 0,
+                    signature: sign(&key1, &member1, first, true, 0),
+                },
+                SignedVote {
+                    voter: member2.clone(),
+                    proposal_id: first,
+                    vote_for: false,
+                    nonce: 0,
+                    signature: sign(&key2, &member2, first, false, 0),
+                },
+            ],
+        );
+        assert!(client.vote_by_sig_batch(&batch).is_empty());
+        assert!(client.has_voted(&first, &member1));
+        let proposal = client.get_proposal(&first);
+        assert_eq!((proposal.votes_for, proposal.votes_against), (1, 1));
+        assert_eq!(client.get_vote_nonce(&member1), 1);
+
+        // A used nonce cannot be replayed, even on another proposal.
+        let replay = sign(&key1, &member1, second, true, 0);
+        assert_eq!(
+            client.try_vote_by_sig(&member1, &second, &true, &0, &replay),
+            Err(Ok(Error::InvalidNonce))
+        );
+        // A signature does not carry over to a different vote.
+        let signed_yes = sign(&key1, &member1, second, true, 1);
+        assert!(client
+            .try_vote_by_sig(&member1, &second, &false, &1, &signed_yes)
+            .is_err());
+        assert_eq!(client.get_vote_nonce(&member1), 1);
+        client.vote_by_sig(&member1, &second, &true, &1, &signed_yes);
+        assert_eq!(client.get_proposal(&second).votes_for, 1);
+
+        let unsigned = sign(&key1, &member3, second, true, 0);
+        assert_eq!(
+            client.try_vote_by_sig(&member3, &second, &true, &0, &unsigned),
+            Err(Ok(Error::VoteKeyNotSet))
+        );
+
+        // Votes that can no longer be cast are skipped, not the whole batch:
+        // member2 front-runs their relayed vote and member1 reuses a nonce.
+        let third = create();
+        client.vote(&member2, &third, &true);
+        let batch = Vec::from_array(
+            &env,
+            [
+                SignedVote {
+                    voter: member2.clone(),
+                    proposal_id: third,
+                    vote_for: false,
+                    nonce: 1,
+                    signature: sign(&key2, &member2, third, false, 1),
+                },
+                SignedVote {
+                    voter: member1.clone(),
+                    proposal_id: third,
+                    vote_for: true,
+                    nonce: 0,
+                    signature: replay.clone(),
+                },
+                SignedVote {
+                    voter: member1.clone(),
+                    proposal_id: third,
+                    vote_for: true,
+                    nonce: 2,
+                    signature: sign(&key1, &member1, third, true, 2),
+                },
+            ],
+        );
+        assert_eq!(client.vote_by_sig_batch(&batch), vec![&env, 0u32, 1]);
+        assert_eq!(client.get_proposal(&third).votes_for, 2);
+        assert_eq!(client.get_vote_nonce(&member1), 3);
+        // The skipped vote's nonce is still unused.
+        assert_eq!(client.get_vote_nonce(&member2), 1);
     }
 
     #[test]
-    fn test_create_proposal_and_vote() {
+    fn test_vote_records_keep_reason_weight_and_time() {
         let (env, admin, acl_id, treasury_id, client) = setup_contract();
 
         let member1 = Address::generate(&env);
         let member2 = Address::generate(&env);
         let member3 = Address::generate(&env);
-        let members = Vec::from_array(
-            &env,
-            [member1.clone(), member2.clone(), member3.clone()],
-        );
-
-        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
-        assign_role(&env, &acl_id, &admin, &member2, &Role::Member);
-        assign_role(&env, &acl_id, &admin, &member3, &Role::Member);
-
-        client.initialize(&admin, &members, &50, &1000, &acl_id, &treasury_id);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone(), member3.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+        env.ledger().with_mut(|l| l.timestamp = 5_000);
 
-        // Create a funding proposal
         let proposal_id = client.create_proposal(
             &member1,
-            &symbol_short!("fund_dev"),
-            &symbol_short!("dev_work"),
-            &ProposalAction::Funding,
-            &500_000,
+            &symbol_short!("t"),
+            &symbol_short!("t"),
+            &ProposalAction::General,
+            &0,
             &member1,
         );
-        assert_eq!(proposal_id, 1);
+        let why = String::from_str(&env, "vendor quote is 30% under budget");
+        client.vote_with_reason(
+            &member1,
+            &proposal_id,
+            &true,
+            &VoteReason::Text(why.clone()),
+        );
+        let digest = BytesN::from_array(&env, &[7; 32]);
+        client.vote_with_reason(
+            &member2,
+            &proposal_id,
+            &false,
+            &VoteReason::Hash(digest.clone()),
+        );
+        client.vote(&member3, &proposal_id, &true);
+
+        assert_eq!(
+            client.get_vote(&proposal_id, &member1),
+            Some(VoteRecord {
+                vote_for: true,
+                choices: Vec::new(&env),
+                weight: 1,
+                reason: VoteReason::Text(why),
+                timestamp: 5_000,
+            })
+        );
+        assert_eq!(
+            client.get_vote(&proposal_id, &member2).unwrap().reason,
+            VoteReason::Hash(digest)
+        );
+        assert_eq!(
+            client.get_vote(&proposal_id, &member3).unwrap().reason,
+            VoteReason::None
+        );
+        assert_eq!(client.get_vote(&proposal_id, &admin), None);
 
-        // Vote
-        client.vote(&member1, &proposal_id, &true);
-        client.vote(&member2, &proposal_id, &true);
+        let long = String::from_bytes(&env, &[b'x'; MAX_REASON_LEN as usize + 1]);
+        let second = client.create_proposal(
+            &member1,
+            &symbol_short!("t"),
+            &symbol_short!("t"),
+            &ProposalAction::General,
+            &0,
+            &member1,
+        );
+        assert_eq!(
+            client.try_vote_with_reason(&member1, &second, &true, &VoteReason::Text(long)),
+            Err(Ok(Error::ReasonTooLong))
+        );
 
-        let proposal = client.get_proposal(&proposal_id);
-        assert_eq!(proposal.votes_for, 2);
-        assert_eq!(proposal.total_votes, 2);
-        assert_eq!(proposal.status, ProposalStatus::Active);
+        // Votes stored as a bare bool before vote records still read back.
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Vote(second, member2.clone()), &false);
+        });
+        let legacy = client.get_vote(&second, &member2).unwrap();
+        assert_eq!(
+            (legacy.vote_for, legacy.weight, legacy.timestamp),
+            (false, 1, 0)
+        );
+    }
+
+    /// A proposal as the first release of the contract stored it.
+    #[contracttype]
+    struct BaselineProposal {
+        id: u64,
+        title: Symbol,
+        description: Symbol,
+        action: ProposalAction,
+        proposer: Address,
+        votes_for: u32,
+        votes_against: u32,
+        total_votes: u32,
+        status: ProposalStatus,
+        created_at: u32,
+        ends_at: u32,
+        amount: i128,
+        target: Address,
     }
 
     #[test]
-    fn test_has_voted() {
+    fn test_proposals_stored_before_upgrade_still_finalize() {
         let (env, admin, acl_id, treasury_id, client) = setup_contract();
 
         let member1 = Address::generate(&env);
-        let members = Vec::from_array(&env, [member1.clone()]);
-
-        assign_role(&env, &acl_id, &admin, &member1, &Role::Member);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        // An Active proposal with one For vote, written as the baseline did.
+        let created_at = env.ledger().sequence();
+        env.as_contract(&client.address, || {
+            let storage = env.storage();
+            storage.instance().set(&DataKey::ProposalCounter, &1_u64);
+            storage.persistent().set(
+                &DataKey::Proposal(1),
+                &BaselineProposal {
+                    id: 1,
+                    title: symbol_short!("old"),
+                    description: symbol_short!("memo"),
+                    action: ProposalAction::General,
+                    proposer: member1.clone(),
+                    votes_for: 1,
+                    votes_against: 0,
+                    total_votes: 1,
+                    status: ProposalStatus::Active,
+                    created_at,
+                    ends_at: created_at + 10,
+                    amount: 0,
+                    target: member1.clone(),
+                },
+            );
+            storage
+                .persistent()
+                .set(&DataKey::Vote(1, member1.clone()), &true);
+        });
+
+        let legacy = client.get_proposal(&1);
+        assert_eq!(legacy.clock, VotingClock::Ledger);
+        assert_eq!(
+            legacy.rules,
+            client.get_action_rules(&ProposalAction::General)
+        );
+        assert_eq!(
+            client.try_vote(&member1, &1, &true),
+            Err(Ok(Error::AlreadyVoted))
+        );
+        client.vote(&member2, &1, &true);
 
-        client.initialize(&admin, &members, &50, &1000, &acl_id, &treasury_id);
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        assert_eq!(client.finalize(&member1, &1), ProposalStatus::Passed);
+        assert_eq!(
+            client.list_proposals(&Some(ProposalStatus::Passed), &0, &10),
+            Vec::from_array(&env, [client.get_proposal(&1)])
+        );
+        assert!(client.get_vote(&1, &member1).unwrap().vote_for);
+        client.execute_proposal(&member1, &1);
+        assert_eq!(client.get_proposal(&1).status, ProposalStatus::Executed);
 
-        let proposal_id = client.create_proposal(
+        // New proposals continue after the stored ones.
+        let next = client.create_proposal(
             &member1,
-            &symbol_short!("test"),
-            &symbol_short!("test"),
+            &symbol_short!("new"),
+            &symbol_short!("memo"),
             &ProposalAction::General,
             &0,
             &member1,
         );
+        assert_eq!(next, 2);
+    }
 
-        assert_eq!(client.has_voted(&proposal_id, &member1), false);
-
-        client.vote(&member1, &proposal_id, &true);
+    #[test]
+    fn test_list_proposals_and_votes_from_indexes() {
+        let (env, admin, acl_id, treasury_id, client) = setup_contract();
 
-        assert_eq!(client.has_voted(&proposal_id, &member1), true);
+        let member1 = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let members = Vec::from_array(&env, [member1.clone(), member2.clone()]);
+        for m in members.iter() {
+            assign_role(&env, &acl_id, &admin, &m, &Role::Member);
+        }
+        client.initialize(&admin, &members, &50, &10, &acl_id, &treasury_id);
+
+        let create = || {
+            client.create_proposal(
+                &member1,
+                &symbol_short!("t"),
+                &symbol_short!("t"),
+                &ProposalAction::General,
+                &0,
+                &member1,
+            )
+        };
+        let ids: std::vec::Vec<u64> = (0..4).map(|_| create()).collect();
+        client.vote(&member1, &ids[0], &true);
+        client.vote(&member2, &ids[0], &true);
+        client.vote(&member1, &ids[1], &false);
+        client.cancel_proposal(&member1, &ids[2], &symbol_short!("dup"));
+
+        let listed = |status: Option<ProposalStatus>, start: u32, limit: u32| {
+            let mut ids: std::vec::Vec<u64> = client
+                .list_proposals(&status, &start, &limit)
+                .iter()
+                .map(|p| p.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(listed(None, 1, 2), [ids[1], ids[2]]);
+        assert_eq!(
+            listed(Some(ProposalStatus::Active), 0, 10),
+            [ids[0], ids[1], ids[3]]
+        );
+        assert_eq!(listed(Some(ProposalStatus::Active), 1, 1).len(), 1);
+        assert_eq!(listed(Some(ProposalStatus::Canceled), 0, 10), [ids[2]]);
+
+        env.ledger().with_mut(|l| l.sequence_number += 11);
+        client.finalize(&member1, &ids[0]);
+        client.finalize(&member1, &ids[1]);
+        client.execute_proposal(&member1, &ids[0]);
+        assert_eq!(listed(Some(ProposalStatus::Active), 0, 10), [ids[3]]);
+        assert_eq!(listed(Some(ProposalStatus::Executed), 0, 10), [ids[0]]);
+        assert_eq!(listed(Some(ProposalStatus::Rejected), 0, 10), [ids[1]]);
+        assert!(listed(Some(ProposalStatus::Passed), 0, 10).is_empty());
+
+        let votes = client.list_votes(&ids[0], &0, &10);
+        assert_eq!(votes.len(), 2);
+        assert_eq!(votes.get(1).unwrap().voter, member2);
+        assert_eq!(client.list_votes(&ids[0], &1, &10).len(), 1);
+
+        let history = client.get_votes_by_voter(&member1, &0, &10);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(0).unwrap().proposal_id, ids[0]);
+        assert!(!history.get(1).unwrap().record.vote_for);
+        assert_eq!(client.get_votes_by_voter(&member1, &1, &1).len(), 1);
     }
 }
@@ -0,0 +1,141 @@
+//! Savings-circle invariants (INV-C1 .. INV-C3).
+
+use soroban_sdk::testutils::Address as _;
+mod common;
+
+use common::*;
+use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+use soroban_sdk::IntoVal;
+
+const CONTRIBUTION: i128 = 100;
+const ROUND: u64 = 1_000;
+const GRACE: u64 = 500;
+const PENALTY: i128 = 10;
+
+fn setup(
+    member_count: usize,
+    max_missed: u32,
+) -> (
+    soroban_sdk::Env,
+    soroban_sdk::Address,                // admin
+    std::vec::Vec<soroban_sdk::Address>, // members
+    TreasuryContractClient<'static>,
+    SavingsCircleContractClient<'static>,
+) {
+    let env = new_env();
+    let admin = soroban_sdk::Address::generate(&env);
+    let (acl_id, acl) = deploy_acl(&env, &admin);
+    let asset = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let treasury = deploy_treasury(
+        &env,
+        &admin,
+        &asset,
+        1,
+        &svec(&env, &[admin.clone()]),
+        &acl_id,
+    );
+    let c = deploy_circle(
+        &env,
+        &admin,
+        &asset,
+        &treasury.address,
+        &acl_id,
+        CONTRIBUTION,
+        ROUND,
+        GRACE,
+        PENALTY,
+        max_missed,
+    );
+
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+    let members = addrs(&env, member_count);
+    for m in &members {
+        acl.assign_role(&admin, m, &Role::Member);
+        token_admin.mint(m, &1_000_000);
+        c.join(m);
+    }
+    (env, admin, members, treasury, c)
+}
+
+/// Seeded contribute / late-contribute / skip sequences. After every step the
+/// pot is fully backed (INV-C1), late penalties land in the treasury (INV-C3),
+/// and over the whole cycle no member is paid twice or paid after exclusion
+/// (INV-C2).
+#[test]
+fn seeded_rounds_conserve_funds() {
+    for seed in [3u64, 11, 58, 404] {
+        let (env, admin, members, treasury, c) = setup(4, 2);
+        let mut rng = Rng::new(seed);
+        c.start(&admin, &rng.flip());
+
+        let mut penalties = 0i128;
+        let mut paid = std::vec::Vec::new();
+        while c.get_config().status == circle::CircleStatus::Active {
+            // 0 = skips the round, 1 = pays late, otherwise pays on time.
+            let plan: std::vec::Vec<u64> = members.iter().map(|_| rng.below(4)).collect();
+            for (m, p) in members.iter().zip(&plan) {
+                if *p > 1 {
+                    let _ = c.try_contribute(m);
+                    assert_circle_invariants(&env, &c);
+                }
+            }
+            advance_time(&env, ROUND + 1);
+            for (m, p) in members.iter().zip(&plan) {
+                if *p == 1 {
+                    if let Ok(Ok(true)) = c.try_contribute(m) {
+                        penalties += PENALTY;
+                    }
+                    assert_circle_invariants(&env, &c);
+                }
+            }
+
+            advance_time(&env, GRACE);
+            if let Some(recipient) = c.close_round(&members[0]) {
+                assert!(!paid.contains(&recipient), "INV-C2 member paid twice");
+                assert!(
+                    !c.get_member(&recipient).excluded,
+                    "INV-C2 excluded member paid"
+                );
+                paid.push(recipient);
+            }
+            assert_circle_invariants(&env, &c);
+            assert_eq!(
+                treasury.get_balance(),
+                penalties,
+                "INV-C3 penalties must reach the treasury"
+            );
+        }
+        assert!(!paid.is_empty());
+    }
+}
+
+/// INV-C3: a late penalty is forwarded to the treasury with only the member's
+/// own authorization; the circle authorizes the treasury's nested transfer itself.
+#[test]
+fn late_penalty_forwarding_needs_only_member_auth() {
+    let (env, admin, members, treasury, c) = setup(2, 0);
+    c.start(&admin, &false);
+    advance_time(&env, ROUND + 1);
+
+    let member = &members[0];
+    let asset = c.get_config().asset;
+    env.mock_auths(&[MockAuth {
+        address: member,
+        invoke: &MockAuthInvoke {
+            contract: &c.address,
+            fn_name: "contribute",
+            args: (member.clone(),).into_val(&env),
+            sub_invokes: &[MockAuthInvoke {
+                contract: &asset,
+                fn_name: "transfer",
+                args: (member.clone(), c.address.clone(), CONTRIBUTION + PENALTY).into_val(&env),
+                sub_invokes: &[],
+            }],
+        },
+    }]);
+    assert!(c.contribute(member));
+    assert_eq!(treasury.get_balance(), PENALTY);
+    assert_eq!(c.get_member(member).penalties, PENALTY);
+}
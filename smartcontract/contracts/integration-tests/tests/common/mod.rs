@@ -12,10 +12,14 @@ use soroban_sdk::{Address, Env, Vec};
 
 pub use stellar_sentinel_access_control as acl;
 pub use stellar_sentinel_governance as governance;
+pub use stellar_sentinel_savings_circle as circle;
 pub use stellar_sentinel_token_vault as vault;
 pub use stellar_sentinel_treasury as treasury;
 
 pub use acl::{AccessControlContract, AccessControlContractClient, Role};
+// Not every test binary touches proposal types; the re-export is shared.
+pub use circle::{SavingsCircleContract, SavingsCircleContractClient};
+#[allow(unused_imports)]
 pub use governance::{
     GovernanceContract, GovernanceContractClient, ProposalAction, ProposalStatus,
 };
@@ -130,7 +134,14 @@ pub fn deploy_governance(
 ) -> GovernanceContractClient<'static> {
     let id = env.register_contract(None, GovernanceContract);
     let c = GovernanceContractClient::new(env, &id);
-    c.initialize(admin, members, &quorum_percent, &voting_period, acl_id, treasury_id);
+    c.initialize(
+        admin,
+        members,
+        &quorum_percent,
+        &voting_period,
+        acl_id,
+        treasury_id,
+    );
     c
 }
 
@@ -144,7 +155,42 @@ pub fn deploy_vault(
 ) -> TokenVaultContractClient<'static> {
     let id = env.register_contract(None, TokenVaultContract);
     let c = TokenVaultContractClient::new(env, &id);
-    c.initialize(admin, asset, emergency_signers, &emergency_threshold, acl_id);
+    c.initialize(
+        admin,
+        asset,
+        emergency_signers,
+        &emergency_threshold,
+        acl_id,
+    );
+    c
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn deploy_circle(
+    env: &Env,
+    admin: &Address,
+    asset: &Address,
+    treasury_id: &Address,
+    acl_id: &Address,
+    contribution: i128,
+    round_duration: u64,
+    grace_period: u64,
+    late_penalty: i128,
+    max_missed: u32,
+) -> SavingsCircleContractClient<'static> {
+    let id = env.register_contract(None, SavingsCircleContract);
+    let c = SavingsCircleContractClient::new(env, &id);
+    c.initialize(
+        admin,
+        asset,
+        treasury_id,
+        acl_id,
+        &contribution,
+        &round_duration,
+        &grace_period,
+        &late_penalty,
+        &max_missed,
+    );
     c
 }
 
@@ -154,13 +200,12 @@ pub fn deploy_vault(
 
 /// INV-T1/T2/T5: balance non-negative, equals deposits minus withdrawals, and the
 /// approval threshold stays within `[1, signer_count]`.
-pub fn assert_treasury_invariants(
-    c: &TreasuryContractClient,
-    deposited: i128,
-    withdrawn: i128,
-) {
+pub fn assert_treasury_invariants(c: &TreasuryContractClient, deposited: i128, withdrawn: i128) {
     let cfg = c.get_config();
-    assert!(cfg.balance >= 0, "INV-T1 treasury balance must be non-negative");
+    assert!(
+        cfg.balance >= 0,
+        "INV-T1 treasury balance must be non-negative"
+    );
     assert_eq!(
         cfg.balance,
         deposited - withdrawn,
@@ -173,22 +218,36 @@ pub fn assert_treasury_invariants(
 }
 
 /// INV-V1: locked liabilities are non-negative and exactly match tracked assets.
-pub fn assert_vault_locked(env: &Env, c: &TokenVaultContractClient, asset: &Address, expected_locked: i128) {
+pub fn assert_vault_locked(
+    env: &Env,
+    c: &TokenVaultContractClient,
+    asset: &Address,
+    expected_locked: i128,
+) {
     let stats = c.get_stats();
-    assert!(stats.total_locked >= 0, "INV-V1 total_locked must be non-negative");
+    assert!(
+        stats.total_locked >= 0,
+        "INV-V1 total_locked must be non-negative"
+    );
     assert_eq!(
         stats.total_locked, expected_locked,
         "INV-V1 total_locked must equal outstanding lock + vesting liabilities"
     );
     let bal = soroban_sdk::token::Client::new(env, asset).balance(&c.address);
-    assert_eq!(bal, expected_locked, "INV-V1 vault balance must equal total_locked");
+    assert_eq!(
+        bal, expected_locked,
+        "INV-V1 vault balance must equal total_locked"
+    );
 }
 
 /// INV-A1: exactly one Owner exists, and per-role counts equal the number of
 /// addresses actually holding each role.
 pub fn assert_acl_consistent(c: &AccessControlContractClient) {
     let summary = c.get_summary();
-    assert_eq!(summary.owner_count, 1, "INV-A1 exactly one owner must exist");
+    assert_eq!(
+        summary.owner_count, 1,
+        "INV-A1 exactly one owner must exist"
+    );
 
     let assignments = c.get_all_assignments();
     let (mut o, mut a, mut m, mut v) = (0u32, 0u32, 0u32, 0u32);
@@ -200,13 +259,47 @@ pub fn assert_acl_consistent(c: &AccessControlContractClient) {
             Role::Viewer => v += 1,
         }
     }
-    assert_eq!(o, summary.owner_count, "INV-A2 owner count matches assignments");
-    assert_eq!(a, summary.admin_count, "INV-A2 admin count matches assignments");
-    assert_eq!(m, summary.member_count, "INV-A2 member count matches assignments");
-    assert_eq!(v, summary.viewer_count, "INV-A2 viewer count matches assignments");
+    assert_eq!(
+        o, summary.owner_count,
+        "INV-A2 owner count matches assignments"
+    );
+    assert_eq!(
+        a, summary.admin_count,
+        "INV-A2 admin count matches assignments"
+    );
+    assert_eq!(
+        m, summary.member_count,
+        "INV-A2 member count matches assignments"
+    );
+    assert_eq!(
+        v, summary.viewer_count,
+        "INV-A2 viewer count matches assignments"
+    );
     assert_eq!(
         assignments.len(),
         summary.total_members,
         "INV-A2 total members matches assignment list"
     );
 }
+
+/// INV-C1: the circle holds exactly the open pot, and everything contributed
+/// was either paid out or is still in that pot.
+pub fn assert_circle_invariants(env: &Env, c: &SavingsCircleContractClient) {
+    let cfg = c.get_config();
+    let (mut contributed, mut received) = (0i128, 0i128);
+    for m in c.get_members().iter() {
+        let ledger = c.get_member(&m);
+        contributed += ledger.contributed;
+        received += ledger.received;
+    }
+    assert_eq!(
+        contributed,
+        received + cfg.pot,
+        "INV-C1 contributions must equal payouts plus the open pot"
+    );
+    let bal = soroban_sdk::token::Client::new(env, &cfg.asset).balance(&c.address);
+    assert_eq!(
+        bal, cfg.pot,
+        "INV-C1 circle balance must equal the open pot"
+    );
+}
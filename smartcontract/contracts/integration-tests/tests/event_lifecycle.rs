@@ -65,7 +65,9 @@ fn treasury_lifecycle_emits_every_step() {
     let admin = soroban_sdk::Address::generate(&env);
     let (acl_id, _acl) = deploy_acl(&env, &admin);
     let signers = addrs(&env, 2);
-    let asset = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let asset = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
     let recipient = soroban_sdk::Address::generate(&env);
     let c = deploy_treasury(&env, &admin, &asset, 2, &svec(&env, &signers), &acl_id);
     let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
@@ -83,7 +85,10 @@ fn treasury_lifecycle_emits_every_step() {
     // deposit + propose + approve + execute = 4 treasury events, plus token
     // transfer event emitted by real token custody. Assert at least 4 new events.
     let new_events = env.events().all().len() - count_before;
-    assert!(new_events >= 4, "expected at least 4 lifecycle events, got {}", new_events);
+    assert!(
+        new_events >= 4,
+        "expected at least 4 lifecycle events, got {new_events}"
+    );
     // Final state agrees with the executed-withdrawal history.
     assert_eq!(c.get_balance(), 3_000);
 }
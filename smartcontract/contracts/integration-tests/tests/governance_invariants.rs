@@ -12,8 +12,8 @@ fn setup(
     period: u32,
 ) -> (
     soroban_sdk::Env,
-    soroban_sdk::Address,                 // admin
-    std::vec::Vec<soroban_sdk::Address>,  // members
+    soroban_sdk::Address,                // admin
+    std::vec::Vec<soroban_sdk::Address>, // members
     GovernanceContractClient<'static>,
 ) {
     let env = new_env();
@@ -24,14 +24,19 @@ fn setup(
         acl.assign_role(&admin, m, &Role::Member);
     }
     let treasury_addr = soroban_sdk::Address::generate(&env);
-    let c = deploy_governance(&env, &admin, &svec(&env, &members), quorum, period, &acl_id, &treasury_addr);
+    let c = deploy_governance(
+        &env,
+        &admin,
+        &svec(&env, &members),
+        quorum,
+        period,
+        &acl_id,
+        &treasury_addr,
+    );
     (env, admin, members, c)
 }
 
-fn mk_proposal(
-    c: &GovernanceContractClient,
-    proposer: &soroban_sdk::Address,
-) -> u64 {
+fn mk_proposal(c: &GovernanceContractClient, proposer: &soroban_sdk::Address) -> u64 {
     c.create_proposal(
         proposer,
         &symbol_short!("t"),
@@ -66,7 +71,11 @@ fn passed_proposal_executes_once() {
     c.vote(&members[1], &id, &true);
 
     let p = c.get_proposal(&id);
-    assert_eq!(p.votes_for + p.votes_against, p.total_votes, "INV-G2 tally consistent");
+    assert_eq!(
+        p.votes_for + p.votes_against,
+        p.total_votes,
+        "INV-G2 tally consistent"
+    );
 
     advance_seq(&env, 200); // past ends_at
     assert_eq!(c.finalize(&members[0], &id), ProposalStatus::Passed);
@@ -99,7 +108,7 @@ fn quorum_not_met_expires() {
 /// INV-G4: voting is closed once the proposal is finalized.
 #[test]
 fn cannot_vote_after_finalize() {
-    let (env, admin, members, c) = setup(3, 50, 100);
+    let (env, _admin, members, c) = setup(3, 50, 100);
     let id = mk_proposal(&c, &members[0]);
     c.vote(&members[0], &id, &true);
     advance_seq(&env, 200);
@@ -121,6 +130,9 @@ fn seeded_operation_sequences() {
         let mut rng = Rng::new(seed);
 
         for _ in 0..40 {
+            // The test budget is cumulative across calls; bound each step
+            // (operation plus invariant sweep) instead.
+            env.budget().reset_default();
             match rng.below(4) {
                 0 => {
                     let p = &members[rng.below(members.len() as u64) as usize];
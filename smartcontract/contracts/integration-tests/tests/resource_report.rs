@@ -106,3 +106,156 @@ fn access_control_storage_growth_is_linear() {
         N, summary.total_members
     );
 }
+
+/// Executing a distribution to 300 members costs about the same as a single
+/// withdrawal: it only advances an accumulator, and each member pulls their
+/// share with `claim`. (Both are measured in the same env, since the test
+/// host's storage lookups grow with the total number of ledger entries.)
+#[test]
+fn treasury_distribution_cost_is_flat_in_member_count() {
+    const MEMBERS: usize = 300;
+    let env = new_env();
+    let admin = soroban_sdk::Address::generate(&env);
+    let (acl_id, _acl) = deploy_acl(&env, &admin);
+    let signers = addrs(&env, 1);
+    let asset = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let c = deploy_treasury(&env, &admin, &asset, 1, &svec(&env, &signers), &acl_id);
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &asset);
+    let depositors = addrs(&env, MEMBERS);
+    // The test budget is cumulative; reset it so only single calls are bounded.
+    for d in &depositors {
+        env.budget().reset_default();
+        token_admin.mint(d, &1_000);
+        c.deposit(d, &1_000);
+    }
+
+    let exp = env.ledger().timestamp() + 1_000;
+    let recipient = soroban_sdk::Address::generate(&env);
+    let withdrawal = c.propose_withdrawal(&signers[0], &recipient, &100, &symbol_short!("w"), &exp);
+    env.budget().reset_default();
+    c.execute(&signers[0], &withdrawal);
+    let withdrawal_cpu = env.budget().cpu_instruction_cost();
+
+    let distribution = c.propose_distribution(
+        &signers[0],
+        &(MEMBERS as i128 * 100),
+        &treasury::DistributionBasis::Equal,
+        &symbol_short!("div"),
+        &exp,
+    );
+    env.budget().reset_default();
+    c.execute(&signers[0], &distribution);
+    let distribution_cpu = env.budget().cpu_instruction_cost();
+
+    for d in &depositors {
+        env.budget().reset_default();
+        assert_eq!(c.claim(d), 100);
+    }
+    assert_eq!(c.get_config().earmarked, 0);
+
+    println!(
+        "[resource] treasury: equal distribution to {MEMBERS} members -> execute cpu={distribution_cpu} (withdrawal cpu={withdrawal_cpu})"
+    );
+    assert!(
+        distribution_cpu < withdrawal_cpu * 2,
+        "distribution execute cost must not scale with member count"
+    );
+}
+
+/// Status and vote indexes are updated in place and read by position, so
+/// status changes, votes and deep pages cost the same with 200 proposals on
+/// record as with one. (First and last operations are compared in the same
+/// env, as above. Votes are For, so they are also counted against the
+/// voter's ragequit while the proposals are open.)
+#[test]
+fn governance_index_cost_is_flat_in_proposal_count() {
+    const PROPOSALS: usize = 200;
+    let env = new_env();
+    let admin = soroban_sdk::Address::generate(&env);
+    let (acl_id, acl) = deploy_acl(&env, &admin);
+    let members = addrs(&env, 3);
+    for m in &members {
+        acl.assign_role(&admin, m, &Role::Member);
+    }
+    let treasury_addr = soroban_sdk::Address::generate(&env);
+    let c = deploy_governance(
+        &env,
+        &admin,
+        &svec(&env, &members),
+        1,
+        10,
+        &acl_id,
+        &treasury_addr,
+    );
+
+    let ids: std::vec::Vec<u64> = (0..PROPOSALS)
+        .map(|_| {
+            env.budget().reset_default();
+            c.create_proposal(
+                &members[0],
+                &symbol_short!("t"),
+                &symbol_short!("d"),
+                &ProposalAction::General,
+                &0,
+                &members[0],
+            )
+        })
+        .collect();
+
+    let measure = |f: &dyn Fn()| {
+        env.budget().reset_default();
+        f();
+        env.budget().cpu_instruction_cost()
+    };
+    for id in &ids[..PROPOSALS - 1] {
+        env.budget().reset_default();
+        c.vote(&members[0], id, &true);
+    }
+    // A member's first vote against another's 200th, on the same proposal.
+    let first_vote_cpu = measure(&|| c.vote(&members[1], &ids[PROPOSALS - 1], &true));
+    let last_vote_cpu = measure(&|| c.vote(&members[0], &ids[PROPOSALS - 1], &true));
+
+    advance_seq(&env, 11);
+    let first_finalize_cpu = measure(&|| {
+        c.finalize(&members[0], &ids[0]);
+    });
+    for id in &ids[1..PROPOSALS - 1] {
+        env.budget().reset_default();
+        c.finalize(&members[0], id);
+    }
+    let last_finalize_cpu = measure(&|| {
+        c.finalize(&members[0], &ids[PROPOSALS - 1]);
+    });
+
+    let passed = Some(ProposalStatus::Passed);
+    let first_page_cpu = measure(&|| assert_eq!(c.list_proposals(&passed, &0, &10).len(), 10));
+    let deep_page_cpu = measure(&|| assert_eq!(c.list_proposals(&passed, &190, &10).len(), 10));
+    let first_history_cpu =
+        measure(&|| assert_eq!(c.get_votes_by_voter(&members[0], &0, &10).len(), 10));
+    let deep_history_cpu =
+        measure(&|| assert_eq!(c.get_votes_by_voter(&members[0], &190, &10).len(), 10));
+
+    println!(
+        "[resource] governance: {PROPOSALS} proposals -> vote cpu {first_vote_cpu}..{last_vote_cpu}, \
+         finalize cpu {first_finalize_cpu}..{last_finalize_cpu}, \
+         list page cpu {first_page_cpu}..{deep_page_cpu}, voter page cpu {first_history_cpu}..{deep_history_cpu}"
+    );
+    assert!(
+        last_vote_cpu < first_vote_cpu * 2,
+        "vote cost must not scale with voter history"
+    );
+    assert!(
+        last_finalize_cpu < first_finalize_cpu * 2,
+        "status change cost must not scale with index size"
+    );
+    assert!(
+        deep_page_cpu < first_page_cpu * 2,
+        "list_proposals cost must not scale with offset"
+    );
+    assert!(
+        deep_history_cpu < first_history_cpu * 2,
+        "get_votes_by_voter cost must not scale with offset"
+    );
+}
@@ -0,0 +1,1045 @@
+#![no_std]
+
+//! Rotating savings circle (ajo/esusu).
+//!
+//! Members commit a fixed contribution every round and the pooled amount is
+//! paid out to one member per round, in a set or randomized order, until every
+//! member still in good standing has received the pot once.
+//!
+//! Round lifecycle:
+//!
+//! | Window                                   | Effect of `contribute`              |
+//! |------------------------------------------|-------------------------------------|
+//! | `round_started_at ..= due`               | On time                             |
+//! | `due < now <= due + grace_period`        | Late: `late_penalty` is charged too |
+//! | `now > due + grace_period`               | Rejected (`ContributionWindowClosed`) |
+//!
+//! After the grace period any member may `close_round`: members who did not
+//! contribute are recorded as missing the round, and a member who reaches
+//! `max_missed` missed rounds is excluded from further contributions and
+//! payouts. Late penalties are donated to the treasury. Every
+//! member keeps a ledger of contributed vs. received amounts.
+//!
+//! Risks:
+//!
+//! - The circle holds no collateral. A member who has already received the
+//!   pot can stop contributing; the only consequences are missed rounds,
+//!   late penalties if they pay late, and eventual exclusion, while the
+//!   members paid after them receive smaller pots. Admit only members trusted
+//!   to see every round through.
+//! - A randomized payout order comes from `env.prng()`, which is seeded from
+//!   ledger data. Validators can predict and influence it, so it is not
+//!   manipulation-resistant; use `set_payout_order` when the order matters.
+
+#[cfg(any(test, feature = "testutils"))]
+extern crate std;
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, vec, Address,
+    Env, IntoVal, Symbol, Vec,
+};
+
+use stellar_sentinel_access_control::AccessControlContractClient;
+use stellar_sentinel_treasury::TreasuryContractClient;
+
+// ============================================================================
+// Error Codes
+// ============================================================================
+
+/// Contract error codes for the Savings Circle module.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// Contract has not been initialized.
+    NotInitialized = 1,
+    /// Contract is already initialized.
+    AlreadyInitialized = 2,
+    /// Caller does not have permission.
+    Unauthorized = 3,
+    /// Contribution or penalty amount is invalid.
+    InvalidAmount = 4,
+    /// Round duration must be greater than zero.
+    InvalidDuration = 5,
+    /// The circle is no longer accepting members.
+    CircleNotOpen = 6,
+    /// The circle is not running rounds.
+    CircleNotActive = 7,
+    /// Address has already joined the circle.
+    AlreadyMember = 8,
+    /// Address is not a member of the circle.
+    NotMember = 9,
+    /// Member has been excluded for missed contributions.
+    MemberExcluded = 10,
+    /// Member already contributed to the current round.
+    AlreadyContributed = 11,
+    /// The current round no longer accepts contributions.
+    ContributionWindowClosed = 12,
+    /// The current round cannot be closed before its grace period ends.
+    RoundStillOpen = 13,
+    /// Payout order is not a permutation of the members.
+    InvalidPayoutOrder = 14,
+    /// A circle needs at least two members to start.
+    NotEnoughMembers = 15,
+}
+
+// ============================================================================
+// Storage Types
+// ============================================================================
+
+/// Storage keys for the savings circle contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    /// Admin address.
+    Admin,
+    /// Token asset address.
+    Asset,
+    /// Whether contract is initialized.
+    Initialized,
+    /// The access-control contract address for membership checks.
+    AclAddress,
+    /// The treasury contract that receives late penalties.
+    TreasuryAddress,
+    /// Fixed contribution per member per round.
+    Contribution,
+    /// Seconds from round start until contributions are due.
+    RoundDuration,
+    /// Seconds after the due time during which late contributions are accepted.
+    GracePeriod,
+    /// Penalty charged on top of a late contribution.
+    LatePenalty,
+    /// Missed rounds that exclude a member (0 = never exclude).
+    MaxMissed,
+    /// Current `CircleStatus`.
+    Status,
+    /// Members in join order.
+    Members,
+    /// Order in which members receive the pot.
+    PayoutOrder,
+    /// Current round number (1-based once started).
+    Round,
+    /// Timestamp the current round started.
+    RoundStart,
+    /// Amount pooled in the current round.
+    Pot,
+    /// Per-member ledger.
+    Member(Address),
+    /// Members who contributed to a round.
+    RoundContributors(u32),
+}
+
+/// Lifecycle of the circle.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CircleStatus {
+    /// Accepting members; no rounds yet.
+    Open,
+    /// Rounds are running.
+    Active,
+    /// Every eligible member has received a payout.
+    Completed,
+}
+
+/// A member's standing and contributed vs. received ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberLedger {
+    /// Member address.
+    pub member: Address,
+    /// Total contributions paid into the pot.
+    pub contributed: i128,
+    /// Total payouts received.
+    pub received: i128,
+    /// Total late penalties paid.
+    pub penalties: i128,
+    /// Rounds contributed to after the due time.
+    pub late_count: u32,
+    /// Rounds closed without a contribution.
+    pub missed_count: u32,
+    /// Round in which the member received the pot (0 = not yet).
+    pub payout_round: u32,
+    /// Whether the member has been excluded for missed rounds.
+    pub excluded: bool,
+}
+
+/// Circle configuration and progress.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircleConfig {
+    /// Admin address.
+    pub admin: Address,
+    /// Token members contribute in.
+    pub asset: Address,
+    /// Treasury contract that receives late penalties.
+    pub treasury: Address,
+    /// Fixed contribution per member per round.
+    pub contribution: i128,
+    /// Seconds from round start until contributions are due.
+    pub round_duration: u64,
+    /// Seconds after the due time during which late contributions are accepted.
+    pub grace_period: u64,
+    /// Penalty charged on top of a late contribution.
+    pub late_penalty: i128,
+    /// Missed rounds that exclude a member (0 = never exclude).
+    pub max_missed: u32,
+    /// Current lifecycle status.
+    pub status: CircleStatus,
+    /// Number of members who joined.
+    pub member_count: u32,
+    /// Current round number (0 before the circle starts).
+    pub current_round: u32,
+    /// Timestamp the current round started.
+    pub round_started_at: u64,
+    /// Amount pooled in the current round.
+    pub pot: i128,
+}
+
+// ============================================================================
+// Contract Implementation
+// ============================================================================
+
+#[contract]
+pub struct SavingsCircleContract;
+
+#[contractimpl]
+impl SavingsCircleContract {
+    // ========================================================================
+    // Initialization
+    // ========================================================================
+
+    /// Initialize the savings circle.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address (must be an ACL admin or above).
+    /// * `asset` - The token members contribute in.
+    /// * `treasury` - The treasury contract that receives late penalties.
+    /// * `acl_address` - The access-control contract; only its members may join.
+    /// * `contribution` - Fixed contribution per member per round.
+    /// * `round_duration` - Seconds from round start until contributions are due.
+    /// * `grace_period` - Seconds after the due time that late contributions are accepted.
+    /// * `late_penalty` - Penalty charged on a late contribution.
+    /// * `max_missed` - Missed rounds that exclude a member (0 = never exclude).
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        treasury: Address,
+        acl_address: Address,
+        contribution: i128,
+        round_duration: u64,
+        grace_period: u64,
+        late_penalty: i128,
+        max_missed: u32,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::AlreadyInitialized);
+        }
+        if contribution <= 0 || late_penalty < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if round_duration == 0 {
+            return Err(Error::InvalidDuration);
+        }
+
+        let acl_client = AccessControlContractClient::new(&env, &acl_address);
+        if !acl_client.is_admin_or_above(&admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Initialized, &true);
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::Asset, &asset);
+        storage.set(&DataKey::TreasuryAddress, &treasury);
+        storage.set(&DataKey::AclAddress, &acl_address);
+        storage.set(&DataKey::Contribution, &contribution);
+        storage.set(&DataKey::RoundDuration, &round_duration);
+        storage.set(&DataKey::GracePeriod, &grace_period);
+        storage.set(&DataKey::LatePenalty, &late_penalty);
+        storage.set(&DataKey::MaxMissed, &max_missed);
+        storage.set(&DataKey::Status, &CircleStatus::Open);
+        storage.set(&DataKey::Members, &Vec::<Address>::new(&env));
+        storage.set(&DataKey::Round, &0_u32);
+        storage.set(&DataKey::Pot, &0_i128);
+
+        env.events().publish(
+            (symbol_short!("circle"), symbol_short!("init")),
+            (admin.clone(), contribution, round_duration),
+        );
+
+        log!(
+            &env,
+            "Savings circle initialized with contribution {}",
+            contribution
+        );
+        Ok(())
+    }
+
+    // ========================================================================
+    // Membership
+    // ========================================================================
+
+    /// Join the circle while it is open. Only access-control members may join.
+    pub fn join(env: Env, member: Address) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_status(&env, CircleStatus::Open, Error::CircleNotOpen)?;
+        Self::require_acl_member_or_above(&env, &member)?;
+
+        member.require_auth();
+
+        let mut members = Self::members(&env);
+        if members.contains(&member) {
+            return Err(Error::AlreadyMember);
+        }
+        members.push_back(member.clone());
+        env.storage().instance().set(&DataKey::Members, &members);
+
+        let ledger = MemberLedger {
+            member: member.clone(),
+            contributed: 0,
+            received: 0,
+            penalties: 0,
+            late_count: 0,
+            missed_count: 0,
+            payout_round: 0,
+            excluded: false,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Member(member.clone()), &ledger);
+
+        env.events().publish(
+            (symbol_short!("circle"), symbol_short!("join")),
+            (member, members.len()),
+        );
+
+        Ok(members.len())
+    }
+
+    /// Set the payout order before the circle starts. The order must list
+    /// every member exactly once.
+    pub fn set_payout_order(env: Env, admin: Address, order: Vec<Address>) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+        Self::require_status(&env, CircleStatus::Open, Error::CircleNotOpen)?;
+
+        admin.require_auth();
+
+        let members = Self::members(&env);
+        if order.len() != members.len() {
+            return Err(Error::InvalidPayoutOrder);
+        }
+        for (i, member) in order.iter().enumerate() {
+            if !members.contains(&member) || order.first_index_of(&member) != Some(i as u32) {
+                return Err(Error::InvalidPayoutOrder);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::PayoutOrder, &order);
+
+        env.events()
+            .publish((symbol_short!("circle"), symbol_short!("order")), order);
+
+        Ok(())
+    }
+
+    /// Close membership and start round 1.
+    ///
+    /// Uses the order from `set_payout_order` (join order if none was set).
+    /// With `randomize`, that order is shuffled with the ledger PRNG, which
+    /// validators can predict; see the module docs.
+    pub fn start(env: Env, admin: Address, randomize: bool) -> Result<Vec<Address>, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &admin)?;
+        Self::require_status(&env, CircleStatus::Open, Error::CircleNotOpen)?;
+
+        admin.require_auth();
+
+        let members = Self::members(&env);
+        if members.len() < 2 {
+            return Err(Error::NotEnoughMembers);
+        }
+
+        let mut order: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PayoutOrder)
+            .unwrap_or(members);
+        if randomize {
+            env.prng().shuffle(&mut order);
+        }
+
+        let storage = env.storage().instance();
+        storage.set(&DataKey::PayoutOrder, &order);
+        storage.set(&DataKey::Status, &CircleStatus::Active);
+        storage.set(&DataKey::Round, &1_u32);
+        storage.set(&DataKey::RoundStart, &env.ledger().timestamp());
+
+        env.events().publish(
+            (symbol_short!("circle"), symbol_short!("start")),
+            (order.clone(), randomize),
+        );
+
+        Ok(order)
+    }
+
+    // ========================================================================
+    // Rounds
+    // ========================================================================
+
+    /// Pay the fixed contribution for the current round.
+    ///
+    /// A contribution after the due time but inside the grace period also pays
+    /// `late_penalty`, which is forwarded to the treasury.
+    ///
+    /// # Returns
+    /// `true` if the contribution was late.
+    pub fn contribute(env: Env, member: Address) -> Result<bool, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_status(&env, CircleStatus::Active, Error::CircleNotActive)?;
+
+        member.require_auth();
+
+        let mut ledger = Self::ledger(&env, &member)?;
+        if ledger.excluded {
+            return Err(Error::MemberExcluded);
+        }
+
+        let round = Self::round(&env);
+        let mut contributors = Self::contributors(&env, round);
+        if contributors.contains(&member) {
+            return Err(Error::AlreadyContributed);
+        }
+
+        let now = env.ledger().timestamp();
+        let due = Self::due_at(&env);
+        let grace: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::GracePeriod)
+            .unwrap_or(0);
+        if now > due.saturating_add(grace) {
+            return Err(Error::ContributionWindowClosed);
+        }
+
+        let contribution = Self::contribution(&env);
+        let late = now > due;
+        let penalty: i128 = if late {
+            env.storage()
+                .instance()
+                .get(&DataKey::LatePenalty)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        let asset = Self::asset(&env)?;
+        token::Client::new(&env, &asset).transfer(
+            &member,
+            &env.current_contract_address(),
+            &(contribution + penalty),
+        );
+        if penalty > 0 {
+            Self::forward_penalty(&env, &asset, penalty)?;
+        }
+
+        contributors.push_back(member.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoundContributors(round), &contributors);
+
+        let pot: i128 = env.storage().instance().get(&DataKey::Pot).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::Pot, &(pot + contribution));
+
+        ledger.contributed += contribution;
+        if late {
+            ledger.late_count += 1;
+            ledger.penalties += penalty;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Member(member.clone()), &ledger);
+
+        env.events().publish(
+            (symbol_short!("circle"), symbol_short!("contrib")),
+            (round, member.clone(), contribution, late),
+        );
+        if penalty > 0 {
+            env.events().publish(
+                (symbol_short!("circle"), symbol_short!("penalty")),
+                (round, member, penalty),
+            );
+        }
+
+        Ok(late)
+    }
+
+    /// Close the current round once its grace period has passed.
+    ///
+    /// Records a missed round for every member in good standing who did not
+    /// contribute (excluding those who reach `max_missed`), then pays the pot
+    /// to the next eligible member in the payout order. The circle completes
+    /// once no eligible member is left to receive a payout; if none is left
+    /// for this round's pot either, each contributor is refunded.
+    ///
+    /// # Returns
+    /// The member who received this round's pot, if any.
+    pub fn close_round(env: Env, caller: Address) -> Result<Option<Address>, Error> {
+        Self::require_initialized(&env)?;
+        Self::require_status(&env, CircleStatus::Active, Error::CircleNotActive)?;
+        Self::ledger(&env, &caller)?;
+
+        caller.require_auth();
+
+        let grace: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::GracePeriod)
+            .unwrap_or(0);
+        if env.ledger().timestamp() <= Self::due_at(&env).saturating_add(grace) {
+            return Err(Error::RoundStillOpen);
+        }
+
+        let round = Self::round(&env);
+        let contributors = Self::contributors(&env, round);
+        let max_missed: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxMissed)
+            .unwrap_or(0);
+
+        for member in Self::members(&env).iter() {
+            let mut ledger = Self::ledger(&env, &member)?;
+            if ledger.excluded || contributors.contains(&member) {
+                continue;
+            }
+            ledger.missed_count += 1;
+            env.events().publish(
+                (symbol_short!("circle"), symbol_short!("missed")),
+                (round, member.clone(), ledger.missed_count),
+            );
+            if max_missed > 0 && ledger.missed_count >= max_missed {
+                ledger.excluded = true;
+                env.events().publish(
+                    (symbol_short!("circle"), symbol_short!("exclude")),
+                    (round, member.clone()),
+                );
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Member(member), &ledger);
+        }
+
+        let pot: i128 = env.storage().instance().get(&DataKey::Pot).unwrap_or(0);
+        let recipient = Self::next_recipient(&env)?;
+        if let Some(recipient) = recipient.clone() {
+            let mut ledger = Self::ledger(&env, &recipient)?;
+            ledger.received += pot;
+            ledger.payout_round = round;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Member(recipient.clone()), &ledger);
+
+            if pot > 0 {
+                let asset = Self::asset(&env)?;
+                token::Client::new(&env, &asset).transfer(
+                    &env.current_contract_address(),
+                    &recipient,
+                    &pot,
+                );
+            }
+            env.storage().instance().set(&DataKey::Pot, &0_i128);
+
+            env.events().publish(
+                (symbol_short!("circle"), symbol_short!("payout")),
+                (round, recipient, pot),
+            );
+        } else if pot > 0 {
+            // Exclusions left no one to pay, so the pot goes back to its
+            // contributors rather than staying locked in the contract.
+            let contribution = Self::contribution(&env);
+            let token = token::Client::new(&env, &Self::asset(&env)?);
+            for member in contributors.iter() {
+                let mut ledger = Self::ledger(&env, &member)?;
+                ledger.contributed -= contribution;
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Member(member.clone()), &ledger);
+                token.transfer(&env.current_contract_address(), &member, &contribution);
+                env.events().publish(
+                    (symbol_short!("circle"), symbol_short!("refund")),
+                    (round, member, contribution),
+                );
+            }
+            env.storage().instance().set(&DataKey::Pot, &0_i128);
+        }
+
+        if Self::next_recipient(&env)?.is_none() {
+            env.storage()
+                .instance()
+                .set(&DataKey::Status, &CircleStatus::Completed);
+            env.events()
+                .publish((symbol_short!("circle"), symbol_short!("complete")), round);
+        } else {
+            env.storage().instance().set(&DataKey::Round, &(round + 1));
+            env.storage()
+                .instance()
+                .set(&DataKey::RoundStart, &env.ledger().timestamp());
+        }
+
+        Ok(recipient)
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get the circle configuration and progress.
+    pub fn get_config(env: Env) -> Result<CircleConfig, Error> {
+        Self::require_initialized(&env)?;
+
+        let storage = env.storage().instance();
+        Ok(CircleConfig {
+            admin: storage.get(&DataKey::Admin).ok_or(Error::NotInitialized)?,
+            asset: Self::asset(&env)?,
+            treasury: storage
+                .get(&DataKey::TreasuryAddress)
+                .ok_or(Error::NotInitialized)?,
+            contribution: Self::contribution(&env),
+            round_duration: storage.get(&DataKey::RoundDuration).unwrap_or(0),
+            grace_period: storage.get(&DataKey::GracePeriod).unwrap_or(0),
+            late_penalty: storage.get(&DataKey::LatePenalty).unwrap_or(0),
+            max_missed: storage.get(&DataKey::MaxMissed).unwrap_or(0),
+            status: storage.get(&DataKey::Status).unwrap_or(CircleStatus::Open),
+            member_count: Self::members(&env).len(),
+            current_round: Self::round(&env),
+            round_started_at: storage.get(&DataKey::RoundStart).unwrap_or(0),
+            pot: storage.get(&DataKey::Pot).unwrap_or(0),
+        })
+    }
+
+    /// Get a member's ledger.
+    pub fn get_member(env: Env, member: Address) -> Result<MemberLedger, Error> {
+        Self::ledger(&env, &member)
+    }
+
+    /// Get all members in join order.
+    pub fn get_members(env: Env) -> Vec<Address> {
+        Self::members(&env)
+    }
+
+    /// Get the payout order (empty until set or started).
+    pub fn get_payout_order(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PayoutOrder)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check whether a member contributed to a round.
+    pub fn has_contributed(env: Env, round: u32, member: Address) -> bool {
+        Self::contributors(&env, round).contains(&member)
+    }
+
+    // ========================================================================
+    // Internal Helpers
+    // ========================================================================
+
+    fn require_initialized(env: &Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::NotInitialized);
+        }
+        Ok(())
+    }
+
+    fn require_status(env: &Env, expected: CircleStatus, err: Error) -> Result<(), Error> {
+        let status: CircleStatus = env
+            .storage()
+            .instance()
+            .get(&DataKey::Status)
+            .ok_or(Error::NotInitialized)?;
+        if status != expected {
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn get_acl(env: &Env) -> Result<AccessControlContractClient, Error> {
+        let acl_address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AclAddress)
+            .ok_or(Error::NotInitialized)?;
+        Ok(AccessControlContractClient::new(env, &acl_address))
+    }
+
+    fn require_acl_member_or_above(env: &Env, caller: &Address) -> Result<(), Error> {
+        let acl = Self::get_acl(env)?;
+        if !acl.is_member_or_above(caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if *caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        let acl = Self::get_acl(env)?;
+        if !acl.is_admin_or_above(caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn asset(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn contribution(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Contribution)
+            .unwrap_or(0)
+    }
+
+    fn members(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Members)
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn round(env: &Env) -> u32 {
+        env.storage().instance().get(&DataKey::Round).unwrap_or(0)
+    }
+
+    fn contributors(env: &Env, round: u32) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoundContributors(round))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn ledger(env: &Env, member: &Address) -> Result<MemberLedger, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Member(member.clone()))
+            .ok_or(Error::NotMember)
+    }
+
+    /// Timestamp at which the current round's contributions are due.
+    fn due_at(env: &Env) -> u64 {
+        let start: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RoundStart)
+            .unwrap_or(0);
+        let duration: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RoundDuration)
+            .unwrap_or(0);
+        start.saturating_add(duration)
+    }
+
+    /// First member in the payout order who is in good standing and has not
+    /// yet received the pot.
+    fn next_recipient(env: &Env) -> Result<Option<Address>, Error> {
+        let order: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PayoutOrder)
+            .unwrap_or(Vec::new(env));
+        for member in order.iter() {
+            let ledger = Self::ledger(env, &member)?;
+            if !ledger.excluded && ledger.payout_round == 0 {
+                return Ok(Some(member));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Donate a collected penalty to the treasury on behalf of this contract,
+    /// so the circle does not become a treasury member.
+    ///
+    /// The treasury pulls the tokens with a nested `transfer`, so the circle
+    /// pre-authorizes that sub-invocation.
+    fn forward_penalty(env: &Env, asset: &Address, amount: i128) -> Result<(), Error> {
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .ok_or(Error::NotInitialized)?;
+        let this = env.current_contract_address();
+
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: asset.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (this.clone(), treasury.clone(), amount).into_val(env),
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
+        TreasuryContractClient::new(env, &treasury).donate(&this, &amount);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+    use stellar_sentinel_access_control::{AccessControlContract, Role};
+    use stellar_sentinel_treasury::TreasuryContract;
+
+    struct Setup {
+        env: Env,
+        admin: Address,
+        members: std::vec::Vec<Address>,
+        client: SavingsCircleContractClient<'static>,
+        token: token::Client<'static>,
+        treasury: TreasuryContractClient<'static>,
+    }
+
+    fn setup(member_count: usize, max_missed: u32) -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let acl_id = env.register_contract(None, AccessControlContract);
+        let acl = AccessControlContractClient::new(&env, &acl_id);
+        acl.initialize(&admin);
+
+        let asset = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let sac = token::StellarAssetClient::new(&env, &asset);
+
+        let treasury_id = env.register_contract(None, TreasuryContract);
+        let treasury = TreasuryContractClient::new(&env, &treasury_id);
+        treasury.initialize(
+            &admin,
+            &asset,
+            &1,
+            &Vec::from_array(&env, [admin.clone()]),
+            &acl_id,
+        );
+
+        let circle_id = env.register_contract(None, SavingsCircleContract);
+        let client = SavingsCircleContractClient::new(&env, &circle_id);
+        client.initialize(
+            &admin,
+            &asset,
+            &treasury_id,
+            &acl_id,
+            &100,
+            &1_000,
+            &500,
+            &10,
+            &max_missed,
+        );
+
+        let mut members = std::vec::Vec::new();
+        for _ in 0..member_count {
+            let m = Address::generate(&env);
+            acl.assign_role(&admin, &m, &Role::Member);
+            sac.mint(&m, &10_000);
+            client.join(&m);
+            members.push(m);
+        }
+
+        Setup {
+            token: token::Client::new(&env, &asset),
+            env,
+            admin,
+            members,
+            client,
+            treasury,
+        }
+    }
+
+    #[test]
+    fn test_full_cycle_pays_every_member_once() {
+        let s = setup(3, 0);
+        let order = Vec::from_array(
+            &s.env,
+            [
+                s.members[2].clone(),
+                s.members[0].clone(),
+                s.members[1].clone(),
+            ],
+        );
+        s.client.set_payout_order(&s.admin, &order);
+        assert_eq!(s.client.start(&s.admin, &false), order);
+
+        for round in 1..=3u32 {
+            for m in &s.members {
+                assert!(!s.client.contribute(m));
+            }
+            s.env.ledger().with_mut(|l| l.timestamp += 1_501);
+            let paid = s.client.close_round(&s.members[0]);
+            assert_eq!(paid, Some(order.get(round - 1).unwrap()));
+        }
+
+        assert_eq!(s.client.get_config().status, CircleStatus::Completed);
+        for (i, m) in order.iter().enumerate() {
+            let ledger = s.client.get_member(&m);
+            assert_eq!(ledger.contributed, 300);
+            assert_eq!(ledger.received, 300);
+            assert_eq!(ledger.payout_round, i as u32 + 1);
+            assert_eq!(s.token.balance(&m), 10_000);
+        }
+        assert_eq!(s.token.balance(&s.client.address), 0);
+    }
+
+    #[test]
+    fn test_late_contribution_penalty_goes_to_treasury() {
+        let s = setup(2, 0);
+        s.client.start(&s.admin, &false);
+
+        assert!(!s.client.contribute(&s.members[0]));
+        s.env.ledger().with_mut(|l| l.timestamp += 1_200);
+        assert!(s.client.contribute(&s.members[1]));
+
+        let ledger = s.client.get_member(&s.members[1]);
+        assert_eq!(ledger.late_count, 1);
+        assert_eq!(ledger.penalties, 10);
+        assert_eq!(s.token.balance(&s.members[1]), 9_890);
+        assert_eq!(s.treasury.get_balance(), 10);
+        assert_eq!(s.client.get_config().pot, 200);
+
+        // The round cannot close until the grace period has passed.
+        assert_eq!(
+            s.client.try_close_round(&s.members[0]),
+            Err(Ok(Error::RoundStillOpen))
+        );
+        s.env.ledger().with_mut(|l| l.timestamp += 301);
+        assert_eq!(
+            s.client.try_contribute(&s.members[0]),
+            Err(Ok(Error::AlreadyContributed))
+        );
+        s.client.close_round(&s.members[0]);
+        assert_eq!(s.client.get_member(&s.members[0]).received, 200);
+    }
+
+    #[test]
+    fn test_missed_rounds_exclude_defaulters() {
+        let s = setup(3, 1);
+        s.client.start(&s.admin, &false);
+
+        // Member 2 never pays and is excluded at the first close.
+        s.client.contribute(&s.members[0]);
+        s.client.contribute(&s.members[1]);
+        s.env.ledger().with_mut(|l| l.timestamp += 1_501);
+        assert_eq!(
+            s.client.close_round(&s.members[1]),
+            Some(s.members[0].clone())
+        );
+
+        let defaulter = s.client.get_member(&s.members[2]);
+        assert_eq!(defaulter.missed_count, 1);
+        assert!(defaulter.excluded);
+        assert_eq!(
+            s.client.try_contribute(&s.members[2]),
+            Err(Ok(Error::MemberExcluded))
+        );
+
+        // Member 1 is the last eligible recipient, so the circle completes.
+        s.client.contribute(&s.members[0]);
+        s.client.contribute(&s.members[1]);
+        s.env.ledger().with_mut(|l| l.timestamp += 1_501);
+        assert_eq!(
+            s.client.close_round(&s.members[0]),
+            Some(s.members[1].clone())
+        );
+        assert_eq!(s.client.get_config().status, CircleStatus::Completed);
+        assert_eq!(s.client.get_member(&s.members[2]).received, 0);
+    }
+
+    #[test]
+    fn test_pot_is_refunded_when_no_recipient_remains() {
+        let s = setup(3, 1);
+        s.client.start(&s.admin, &false);
+
+        // Members 0 and 1 are paid in rounds 1 and 2.
+        for _ in 0..2 {
+            for m in &s.members {
+                s.client.contribute(m);
+            }
+            s.env.ledger().with_mut(|l| l.timestamp += 1_501);
+            s.client.close_round(&s.members[0]);
+        }
+
+        // Member 2, the last recipient, misses round 3 and is excluded.
+        s.client.contribute(&s.members[0]);
+        s.client.contribute(&s.members[1]);
+        s.env.ledger().with_mut(|l| l.timestamp += 1_501);
+        assert_eq!(s.client.close_round(&s.members[0]), None);
+
+        let config = s.client.get_config();
+        assert_eq!(config.status, CircleStatus::Completed);
+        assert_eq!(config.pot, 0);
+        assert_eq!(s.token.balance(&s.client.address), 0);
+        // Each got their round-3 contribution back; the excluded member's
+        // earlier contributions stay with the members they paid.
+        for m in &s.members[..2] {
+            assert_eq!(s.client.get_member(m).contributed, 200);
+            assert_eq!(s.token.balance(m), 10_100);
+        }
+        assert_eq!(s.token.balance(&s.members[2]), 9_800);
+    }
+
+    #[test]
+    fn test_membership_and_order_guards() {
+        let s = setup(2, 0);
+        let outsider = Address::generate(&s.env);
+        assert_eq!(s.client.try_join(&outsider), Err(Ok(Error::Unauthorized)));
+        assert_eq!(
+            s.client.try_join(&s.members[0]),
+            Err(Ok(Error::AlreadyMember))
+        );
+        assert_eq!(
+            s.client.try_set_payout_order(
+                &s.admin,
+                &Vec::from_array(&s.env, [s.members[0].clone(), s.members[0].clone()])
+            ),
+            Err(Ok(Error::InvalidPayoutOrder))
+        );
+
+        let order = s.client.start(&s.admin, &true);
+        assert_eq!(order.len(), 2);
+        assert!(order.contains(&s.members[0]) && order.contains(&s.members[1]));
+        assert_eq!(
+            s.client.try_start(&s.admin, &false),
+            Err(Ok(Error::CircleNotOpen))
+        );
+        assert_eq!(
+            s.client.try_close_round(&outsider),
+            Err(Ok(Error::NotMember))
+        );
+    }
+}
@@ -4,10 +4,8 @@
 extern crate std;
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, contracterror, symbol_short,
-    token,
-    Address, Env, Symbol, Vec,
-    log,
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, Address, Env,
+    Symbol, Vec,
 };
 
 use stellar_sentinel_access_control::AccessControlContractClient;
@@ -185,16 +183,14 @@ impl TokenVaultContract {
         env.storage()
             .instance()
             .set(&DataKey::EmergencyThreshold, &emergency_threshold);
-        env.storage()
-            .instance()
-            .set(&DataKey::LockCounter, &0_u64);
+        env.storage().instance().set(&DataKey::LockCounter, &0_u64);
         env.storage()
             .instance()
             .set(&DataKey::VestingCounter, &0_u64);
+        env.storage().instance().set(&DataKey::TotalLocked, &0_i128);
         env.storage()
             .instance()
-            .set(&DataKey::TotalLocked, &0_i128);
-        env.storage().instance().set(&DataKey::AclAddress, &acl_address);
+            .set(&DataKey::AclAddress, &acl_address);
 
         env.events().publish(
             (symbol_short!("vault"), symbol_short!("init")),
@@ -279,7 +275,13 @@ impl TokenVaultContract {
             (lock_id, owner.clone(), amount, duration),
         );
 
-        log!(&env, "Tokens locked: {} for {} seconds (lock #{})", amount, duration, lock_id);
+        log!(
+            &env,
+            "Tokens locked: {} for {} seconds (lock #{})",
+            amount,
+            duration,
+            lock_id
+        );
         Ok(lock_id)
     }
 
@@ -339,7 +341,13 @@ impl TokenVaultContract {
             (lock_id, owner.clone(), amount),
         );
 
-        log!(&env, "Lock #{} claimed by {:?}: {} tokens", lock_id, owner, amount);
+        log!(
+            &env,
+            "Lock #{} claimed by {:?}: {} tokens",
+            lock_id,
+            owner,
+            amount
+        );
         Ok(amount)
     }
 
@@ -349,11 +357,7 @@ impl TokenVaultContract {
 
     /// Approve an emergency unlock for a specific lock.
     /// Requires multi-sig from emergency signers.
-    pub fn approve_emergency(
-        env: Env,
-        signer: Address,
-        lock_id: u64,
-    ) -> Result<u32, Error> {
+    pub fn approve_emergency(env: Env, signer: Address, lock_id: u64) -> Result<u32, Error> {
         Self::require_initialized(&env)?;
 
         signer.require_auth();
@@ -418,11 +422,7 @@ impl TokenVaultContract {
     }
 
     /// Execute emergency unlock after enough approvals.
-    pub fn emergency_unlock(
-        env: Env,
-        caller: Address,
-        lock_id: u64,
-    ) -> Result<i128, Error> {
+    pub fn emergency_unlock(env: Env, caller: Address, lock_id: u64) -> Result<i128, Error> {
         Self::require_initialized(&env)?;
 
         caller.require_auth();
@@ -564,16 +564,19 @@ impl TokenVaultContract {
             (vesting_id, beneficiary.clone(), total_amount, duration),
         );
 
-        log!(&env, "Vesting #{} created for {:?}: {} over {} seconds", vesting_id, beneficiary, total_amount, duration);
+        log!(
+            &env,
+            "Vesting #{} created for {:?}: {} over {} seconds",
+            vesting_id,
+            beneficiary,
+            total_amount,
+            duration
+        );
         Ok(vesting_id)
     }
 
     /// Claim available vested tokens.
-    pub fn claim_vested(
-        env: Env,
-        beneficiary: Address,
-        vesting_id: u64,
-    ) -> Result<i128, Error> {
+    pub fn claim_vested(env: Env, beneficiary: Address, vesting_id: u64) -> Result<i128, Error> {
         Self::require_initialized(&env)?;
 
         beneficiary.require_auth();
@@ -623,7 +626,11 @@ impl TokenVaultContract {
             .instance()
             .get(&DataKey::TotalLocked)
             .unwrap_or(0);
-        let new_total = if total > claimable { total - claimable } else { 0 };
+        let new_total = if total > claimable {
+            total - claimable
+        } else {
+            0
+        };
         env.storage()
             .instance()
             .set(&DataKey::TotalLocked, &new_total);
@@ -641,11 +648,7 @@ impl TokenVaultContract {
     }
 
     /// Revoke a vesting schedule, returning unclaimed tokens to admin.
-    pub fn revoke_vesting(
-        env: Env,
-        admin: Address,
-        vesting_id: u64,
-    ) -> Result<i128, Error> {
+    pub fn revoke_vesting(env: Env, admin: Address, vesting_id: u64) -> Result<i128, Error> {
         Self::require_initialized(&env)?;
         Self::require_admin(&env, &admin)?;
 
@@ -677,7 +680,11 @@ impl TokenVaultContract {
             .instance()
             .get(&DataKey::TotalLocked)
             .unwrap_or(0);
-        let new_total = if total > remaining { total - remaining } else { 0 };
+        let new_total = if total > remaining {
+            total - remaining
+        } else {
+            0
+        };
         env.storage()
             .instance()
             .set(&DataKey::TotalLocked, &new_total);
@@ -764,9 +771,7 @@ impl TokenVaultContract {
 
         Self::require_acl_admin_or_above(&env, &new_admin)?;
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Admin, &new_admin);
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
 
         env.events().publish(
             (symbol_short!("vault"), symbol_short!("admin")),
@@ -849,9 +854,7 @@ mod test {
     use super::*;
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::Env;
-    use stellar_sentinel_access_control::{
-        AccessControlContract, AccessControlContractClient, Role,
-    };
+    use stellar_sentinel_access_control::{AccessControlContract, AccessControlContractClient};
 
     fn deploy_acl(env: &Env, owner: &Address) -> Address {
         let acl_id = env.register_contract(None, AccessControlContract);
@@ -860,12 +863,13 @@ mod test {
         acl_id
     }
 
-    fn assign_role(env: &Env, acl_id: &Address, admin: &Address, target: &Address, role: &Role) {
-        let acl_client = AccessControlContractClient::new(env, acl_id);
-        acl_client.assign_role(admin, target, role);
-    }
-
-    fn setup_contract_with_token() -> (Env, Address, Address, TokenVaultContractClient<'static>, Address) {
+    fn setup_contract_with_token() -> (
+        Env,
+        Address,
+        Address,
+        TokenVaultContractClient<'static>,
+        Address,
+    ) {
         let env = Env::default();
         env.mock_all_auths();
         let admin = Address::generate(&env);
@@ -915,7 +919,7 @@ mod test {
 
         let lock = client.get_lock(&lock_id);
         assert_eq!(lock.amount, 1_000_000);
-        assert_eq!(lock.claimed, false);
+        assert!(!lock.claimed);
         assert_eq!(lock.owner, owner);
 
         let stats = client.get_stats();